@@ -58,6 +58,9 @@ module Protocol = struct
 
         external to_json : int -> t -> string
           = "caml_pasta_fp_plonk_circuit_serialize"
+
+        external of_json : string -> int * t
+          = "caml_pasta_fp_plonk_circuit_deserialize"
       end
 
       module Fq = struct
@@ -81,6 +84,9 @@ module Protocol = struct
 
         external to_json : int -> t -> string
           = "caml_pasta_fq_plonk_circuit_serialize"
+
+        external of_json : string -> int * t
+          = "caml_pasta_fq_plonk_circuit_deserialize"
       end
     end
   end
@@ -204,6 +210,24 @@ module Protocol = struct
         -> t
         = "caml_pasta_fp_plonk_index_create_bytecode" "caml_pasta_fp_plonk_index_create"
 
+      external try_create :
+           Gates.Vector.Fp.t
+        -> int
+        -> Pasta_bindings.Fp.t Kimchi_types.lookup_table array
+        -> Pasta_bindings.Fp.t Kimchi_types.runtime_table_cfg array
+        -> int
+        -> SRS.Fp.t
+        -> (t, Kimchi_types.SetupError.t) result
+        = "caml_pasta_fp_plonk_index_try_create_bytecode" "caml_pasta_fp_plonk_index_try_create"
+
+      external create_from_circuit_json :
+           string
+        -> Pasta_bindings.Fp.t Kimchi_types.lookup_table array
+        -> Pasta_bindings.Fp.t Kimchi_types.runtime_table_cfg array
+        -> int
+        -> SRS.Fp.t
+        -> t = "caml_pasta_fp_plonk_index_create_from_circuit_json"
+
       external max_degree : t -> int = "caml_pasta_fp_plonk_index_max_degree"
 
       external public_inputs : t -> int
@@ -238,6 +262,24 @@ module Protocol = struct
         -> t
         = "caml_pasta_fq_plonk_index_create_bytecode" "caml_pasta_fq_plonk_index_create"
 
+      external try_create :
+           Gates.Vector.Fq.t
+        -> int
+        -> Pasta_bindings.Fq.t Kimchi_types.lookup_table array
+        -> Pasta_bindings.Fq.t Kimchi_types.runtime_table_cfg array
+        -> int
+        -> SRS.Fq.t
+        -> (t, Kimchi_types.SetupError.t) result
+        = "caml_pasta_fq_plonk_index_try_create_bytecode" "caml_pasta_fq_plonk_index_try_create"
+
+      external create_from_circuit_json :
+           string
+        -> Pasta_bindings.Fq.t Kimchi_types.lookup_table array
+        -> Pasta_bindings.Fq.t Kimchi_types.runtime_table_cfg array
+        -> int
+        -> SRS.Fq.t
+        -> t = "caml_pasta_fq_plonk_index_create_from_circuit_json"
+
       external max_degree : t -> int = "caml_pasta_fq_plonk_index_max_degree"
 
       external public_inputs : t -> int
@@ -269,20 +311,55 @@ module Protocol = struct
         )
         Kimchi_types.VerifierIndex.verifier_index
 
+      type nonrec handle
+
       external create : Index.Fp.t -> t
         = "caml_pasta_fp_plonk_verifier_index_create"
 
+      external create_handle : Index.Fp.t -> handle
+        = "caml_pasta_fp_plonk_verifier_index_create_handle"
+
+      external handle_of_index : t -> handle
+        = "caml_pasta_fp_plonk_verifier_index_handle_of_index"
+
+      external handle_to_index : handle -> t
+        = "caml_pasta_fp_plonk_verifier_index_handle_to_index"
+
       external read : int option -> SRS.Fp.t -> string -> t
         = "caml_pasta_fp_plonk_verifier_index_read"
 
       external write : bool option -> t -> string -> unit
         = "caml_pasta_fp_plonk_verifier_index_write"
 
+      external to_bytes : t -> string
+        = "caml_pasta_fp_plonk_verifier_index_to_bytes"
+
+      external of_bytes : SRS.Fp.t -> string -> t
+        = "caml_pasta_fp_plonk_verifier_index_of_bytes"
+
+      external to_json : t -> string
+        = "caml_pasta_fp_plonk_verifier_index_to_json"
+
+      external of_json : SRS.Fp.t -> string -> t
+        = "caml_pasta_fp_plonk_verifier_index_of_json"
+
+      external digest : t -> Pasta_bindings.Fq.t
+        = "caml_pasta_fp_plonk_verifier_index_digest"
+
+      external bytes_digest : t -> bytes
+        = "caml_pasta_fp_plonk_verifier_index_bytes_digest"
+
+      external diff : t -> t -> Kimchi_types.verifier_index_change array
+        = "caml_pasta_fp_plonk_verifier_index_diff"
+
       external shifts : int -> Pasta_bindings.Fp.t array
         = "caml_pasta_fp_plonk_verifier_index_shifts"
 
       external dummy : unit -> t = "caml_pasta_fp_plonk_verifier_index_dummy"
 
+      external dummy_with_shape : Kimchi_types.DummyShape.t -> SRS.Fp.t -> t
+        = "caml_pasta_fp_plonk_verifier_index_dummy_with_shape"
+
       external deep_copy : t -> t
         = "caml_pasta_fp_plonk_verifier_index_deep_copy"
     end
@@ -295,20 +372,55 @@ module Protocol = struct
         )
         Kimchi_types.VerifierIndex.verifier_index
 
+      type nonrec handle
+
       external create : Index.Fq.t -> t
         = "caml_pasta_fq_plonk_verifier_index_create"
 
+      external create_handle : Index.Fq.t -> handle
+        = "caml_pasta_fq_plonk_verifier_index_create_handle"
+
+      external handle_of_index : t -> handle
+        = "caml_pasta_fq_plonk_verifier_index_handle_of_index"
+
+      external handle_to_index : handle -> t
+        = "caml_pasta_fq_plonk_verifier_index_handle_to_index"
+
       external read : int option -> SRS.Fq.t -> string -> t
         = "caml_pasta_fq_plonk_verifier_index_read"
 
       external write : bool option -> t -> string -> unit
         = "caml_pasta_fq_plonk_verifier_index_write"
 
+      external to_bytes : t -> string
+        = "caml_pasta_fq_plonk_verifier_index_to_bytes"
+
+      external of_bytes : SRS.Fq.t -> string -> t
+        = "caml_pasta_fq_plonk_verifier_index_of_bytes"
+
+      external to_json : t -> string
+        = "caml_pasta_fq_plonk_verifier_index_to_json"
+
+      external of_json : SRS.Fq.t -> string -> t
+        = "caml_pasta_fq_plonk_verifier_index_of_json"
+
+      external digest : t -> Pasta_bindings.Fp.t
+        = "caml_pasta_fq_plonk_verifier_index_digest"
+
+      external bytes_digest : t -> bytes
+        = "caml_pasta_fq_plonk_verifier_index_bytes_digest"
+
+      external diff : t -> t -> Kimchi_types.verifier_index_change array
+        = "caml_pasta_fq_plonk_verifier_index_diff"
+
       external shifts : int -> Pasta_bindings.Fq.t array
         = "caml_pasta_fq_plonk_verifier_index_shifts"
 
       external dummy : unit -> t = "caml_pasta_fq_plonk_verifier_index_dummy"
 
+      external dummy_with_shape : Kimchi_types.DummyShape.t -> SRS.Fq.t -> t
+        = "caml_pasta_fq_plonk_verifier_index_dummy_with_shape"
+
       external deep_copy : t -> t
         = "caml_pasta_fq_plonk_verifier_index_deep_copy"
     end
@@ -344,6 +456,61 @@ module Protocol = struct
            Kimchi_types.proof_with_public
         -> t = "fp_oracles_create"
 
+      external create_with_public :
+           Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           array
+        -> ( Pasta_bindings.Fp.t
+           , SRS.Fp.t
+           , Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.prover_proof
+        -> Pasta_bindings.Fp.t array Kimchi_types.point_evaluations option
+        -> Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           option
+        -> t = "fp_oracles_create_with_public"
+
+      external create_with_handle :
+           Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           array
+        -> VerifierIndex.Fp.handle
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.prover_proof
+        -> t = "fp_oracles_create_with_handle"
+
+      external create_batch :
+           Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           array
+        -> ( Pasta_bindings.Fp.t
+           , SRS.Fp.t
+           , Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.prover_proof
+           array
+        -> t array = "fp_oracles_create_batch"
+
+      external create_debug :
+           Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           array
+        -> ( Pasta_bindings.Fp.t
+           , SRS.Fp.t
+           , Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.prover_proof
+        -> ( Pasta_bindings.Fp.t
+           , Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.oracles_with_transcript = "fp_oracles_create_debug"
+
       external dummy : unit -> Pasta_bindings.Fp.t Kimchi_types.random_oracles
         = "fp_oracles_dummy"
 
@@ -382,6 +549,61 @@ module Protocol = struct
            Kimchi_types.proof_with_public
         -> t = "fq_oracles_create"
 
+      external create_with_public :
+           Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           array
+        -> ( Pasta_bindings.Fq.t
+           , SRS.Fq.t
+           , Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.prover_proof
+        -> Pasta_bindings.Fq.t array Kimchi_types.point_evaluations option
+        -> Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           option
+        -> t = "fq_oracles_create_with_public"
+
+      external create_with_handle :
+           Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           array
+        -> VerifierIndex.Fq.handle
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.prover_proof
+        -> t = "fq_oracles_create_with_handle"
+
+      external create_batch :
+           Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           array
+        -> ( Pasta_bindings.Fq.t
+           , SRS.Fq.t
+           , Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.prover_proof
+           array
+        -> t array = "fq_oracles_create_batch"
+
+      external create_debug :
+           Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           array
+        -> ( Pasta_bindings.Fq.t
+           , SRS.Fq.t
+           , Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.prover_proof
+        -> ( Pasta_bindings.Fq.t
+           , Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.oracles_with_transcript = "fq_oracles_create_debug"
+
       external dummy : unit -> Pasta_bindings.Fq.t Kimchi_types.random_oracles
         = "fq_oracles_dummy"
 
@@ -392,6 +614,26 @@ module Protocol = struct
     end
   end
 
+  module ProverPool = struct
+    external configure : Kimchi_types.ProverPool.config -> unit
+      = "caml_prover_pool_configure"
+
+    external select : string option -> unit = "caml_prover_pool_select"
+
+    external num_threads : unit -> int = "caml_prover_pool_num_threads"
+  end
+
+  module ProvingTask = struct
+    type nonrec t
+
+    external create : unit -> t = "caml_proving_task_create"
+
+    external cancel : t -> unit = "caml_proving_task_cancel"
+
+    external status : t -> Kimchi_types.proving_status
+      = "caml_proving_task_status"
+  end
+
   module Proof = struct
     module Fp = struct
       external create :
@@ -404,6 +646,56 @@ module Protocol = struct
            , Pasta_bindings.Fp.t )
            Kimchi_types.proof_with_public = "caml_pasta_fp_plonk_proof_create"
 
+      external create_owned :
+           Index.Fp.t
+        -> FieldVectors.Fp.t array
+        -> Pasta_bindings.Fp.t Kimchi_types.runtime_table array
+        -> Pasta_bindings.Fp.t array
+        -> Pasta_bindings.Fq.t Kimchi_types.or_infinity array
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+        = "caml_pasta_fp_plonk_proof_create_owned"
+
+      external create_debug :
+           Index.Fp.t
+        -> FieldVectors.Fp.t array
+        -> Pasta_bindings.Fp.t Kimchi_types.runtime_table array
+        -> Pasta_bindings.Fp.t array
+        -> Pasta_bindings.Fq.t Kimchi_types.or_infinity array
+        -> ( ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fp.t )
+             Kimchi_types.proof_with_public
+           , Pasta_bindings.Fp.t Kimchi_types.Witness.unsatisfied )
+           result = "caml_pasta_fp_plonk_proof_create_debug"
+
+      external create_with_task :
+           Index.Fp.t
+        -> FieldVectors.Fp.t array
+        -> Pasta_bindings.Fp.t Kimchi_types.runtime_table array
+        -> Pasta_bindings.Fp.t array
+        -> Pasta_bindings.Fq.t Kimchi_types.or_infinity array
+        -> ProvingTask.t
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+           option
+        = "caml_pasta_fp_plonk_proof_create_with_task_bytecode" "caml_pasta_fp_plonk_proof_create_with_task"
+
+      external create_batch :
+           Index.Fp.t
+        -> ( FieldVectors.Fp.t
+           , Pasta_bindings.Fp.t
+           , Pasta_bindings.Fq.t Kimchi_types.or_infinity )
+           Kimchi_types.ProverInput.t
+           array
+        -> ( ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fp.t )
+             Kimchi_types.proof_with_public
+           , string )
+           result
+           array = "caml_pasta_fp_plonk_proof_create_batch"
+
       external create_and_verify :
            Index.Fp.t
         -> FieldVectors.Fp.t array
@@ -499,12 +791,135 @@ module Protocol = struct
            array
         -> bool = "caml_pasta_fp_plonk_proof_batch_verify"
 
+      external verify_result :
+           ( Pasta_bindings.Fp.t
+           , SRS.Fp.t
+           , Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+        -> (unit, Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fp_plonk_proof_verify_result"
+
+      external check_shape :
+           ( Pasta_bindings.Fp.t
+           , SRS.Fp.t
+           , Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+        -> (unit, Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fp_plonk_proof_check_shape"
+
+      external check_shape_with_handle :
+           VerifierIndex.Fp.handle
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+        -> (unit, Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fp_plonk_proof_check_shape_with_handle"
+
+      external batch_verify_result :
+           ( Pasta_bindings.Fp.t
+           , SRS.Fp.t
+           , Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+           array
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+           array
+        -> (unit, int * Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fp_plonk_proof_batch_verify_result"
+
+      external batch_verify_bisect :
+           ( Pasta_bindings.Fp.t
+           , SRS.Fp.t
+           , Pasta_bindings.Fq.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+           array
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+           array
+        -> int array = "caml_pasta_fp_plonk_proof_batch_verify_bisect"
+
+      external verify_with_handle :
+           VerifierIndex.Fp.handle
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+        -> bool = "caml_pasta_fp_plonk_proof_verify_with_handle"
+
+      external batch_verify_with_handle :
+           VerifierIndex.Fp.handle
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+           array
+        -> bool = "caml_pasta_fp_plonk_proof_batch_verify_with_handle"
+
+      external verify_result_with_handle :
+           VerifierIndex.Fp.handle
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+        -> (unit, Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fp_plonk_proof_verify_result_with_handle"
+
+      external batch_verify_result_with_handle :
+           VerifierIndex.Fp.handle
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+           array
+        -> (unit, int * Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fp_plonk_proof_batch_verify_result_with_handle"
+
+      external to_bytes :
+           ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+        -> string = "caml_pasta_fp_plonk_proof_to_bytes"
+
+      external of_bytes :
+           string
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public = "caml_pasta_fp_plonk_proof_of_bytes"
+
+      external to_json :
+           ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+        -> string = "caml_pasta_fp_plonk_proof_to_json"
+
+      external of_json :
+           string
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public = "caml_pasta_fp_plonk_proof_of_json"
+
       external dummy :
            unit
         -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
            , Pasta_bindings.Fp.t )
            Kimchi_types.proof_with_public = "caml_pasta_fp_plonk_proof_dummy"
 
+      external dummy_with_shape :
+           Kimchi_types.DummyShape.t
+        -> int
+        -> ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+        = "caml_pasta_fp_plonk_proof_dummy_with_shape"
+
       external deep_copy :
            ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
            , Pasta_bindings.Fp.t )
@@ -526,6 +941,127 @@ module Protocol = struct
            , Pasta_bindings.Fq.t )
            Kimchi_types.proof_with_public = "caml_pasta_fq_plonk_proof_create"
 
+      external create_owned :
+           Index.Fq.t
+        -> FieldVectors.Fq.t array
+        -> Pasta_bindings.Fq.t Kimchi_types.runtime_table array
+        -> Pasta_bindings.Fq.t array
+        -> Pasta_bindings.Fp.t Kimchi_types.or_infinity array
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+        = "caml_pasta_fq_plonk_proof_create_owned"
+
+      external create_debug :
+           Index.Fq.t
+        -> FieldVectors.Fq.t array
+        -> Pasta_bindings.Fq.t Kimchi_types.runtime_table array
+        -> Pasta_bindings.Fq.t array
+        -> Pasta_bindings.Fp.t Kimchi_types.or_infinity array
+        -> ( ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fq.t )
+             Kimchi_types.proof_with_public
+           , Pasta_bindings.Fq.t Kimchi_types.Witness.unsatisfied )
+           result = "caml_pasta_fq_plonk_proof_create_debug"
+
+      external create_with_task :
+           Index.Fq.t
+        -> FieldVectors.Fq.t array
+        -> Pasta_bindings.Fq.t Kimchi_types.runtime_table array
+        -> Pasta_bindings.Fq.t array
+        -> Pasta_bindings.Fp.t Kimchi_types.or_infinity array
+        -> ProvingTask.t
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+           option
+        = "caml_pasta_fq_plonk_proof_create_with_task_bytecode" "caml_pasta_fq_plonk_proof_create_with_task"
+
+      external create_batch :
+           Index.Fq.t
+        -> ( FieldVectors.Fq.t
+           , Pasta_bindings.Fq.t
+           , Pasta_bindings.Fp.t Kimchi_types.or_infinity )
+           Kimchi_types.ProverInput.t
+           array
+        -> ( ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fq.t )
+             Kimchi_types.proof_with_public
+           , string )
+           result
+           array = "caml_pasta_fq_plonk_proof_create_batch"
+
+      external create_and_verify :
+           Index.Fq.t
+        -> FieldVectors.Fq.t array
+        -> Pasta_bindings.Fq.t Kimchi_types.runtime_table array
+        -> Pasta_bindings.Fq.t array
+        -> Pasta_bindings.Fp.t Kimchi_types.or_infinity array
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+        = "caml_pasta_fq_plonk_proof_create_and_verify"
+
+      external example_with_lookup :
+           SRS.Fq.t
+        -> Index.Fq.t
+           * Pasta_bindings.Fq.t
+           * ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fq.t )
+             Kimchi_types.proof_with_public
+        = "caml_pasta_fq_plonk_proof_example_with_lookup"
+
+      external example_with_ffadd :
+           SRS.Fq.t
+        -> Index.Fq.t
+           * Pasta_bindings.Fq.t
+           * ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fq.t )
+             Kimchi_types.proof_with_public
+        = "caml_pasta_fq_plonk_proof_example_with_ffadd"
+
+      external example_with_xor :
+           SRS.Fq.t
+        -> Index.Fq.t
+           * (Pasta_bindings.Fq.t * Pasta_bindings.Fq.t)
+           * ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fq.t )
+             Kimchi_types.proof_with_public
+        = "caml_pasta_fq_plonk_proof_example_with_xor"
+
+      external example_with_rot :
+           SRS.Fq.t
+        -> Index.Fq.t
+           * (Pasta_bindings.Fq.t * Pasta_bindings.Fq.t)
+           * ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fq.t )
+             Kimchi_types.proof_with_public
+        = "caml_pasta_fq_plonk_proof_example_with_rot"
+
+      external example_with_foreign_field_mul :
+           SRS.Fq.t
+        -> Index.Fq.t
+           * ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fq.t )
+             Kimchi_types.proof_with_public
+        = "caml_pasta_fq_plonk_proof_example_with_foreign_field_mul"
+
+      external example_with_range_check :
+           SRS.Fq.t
+        -> Index.Fq.t
+           * ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fq.t )
+             Kimchi_types.proof_with_public
+        = "caml_pasta_fq_plonk_proof_example_with_range_check"
+
+      external example_with_range_check0 :
+           SRS.Fq.t
+        -> Index.Fq.t
+           * ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+             , Pasta_bindings.Fq.t )
+             Kimchi_types.proof_with_public
+        = "caml_pasta_fq_plonk_proof_example_with_range_check0"
+
       external verify :
            ( Pasta_bindings.Fq.t
            , SRS.Fq.t
@@ -550,12 +1086,135 @@ module Protocol = struct
            array
         -> bool = "caml_pasta_fq_plonk_proof_batch_verify"
 
+      external verify_result :
+           ( Pasta_bindings.Fq.t
+           , SRS.Fq.t
+           , Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+        -> (unit, Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fq_plonk_proof_verify_result"
+
+      external check_shape :
+           ( Pasta_bindings.Fq.t
+           , SRS.Fq.t
+           , Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+        -> (unit, Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fq_plonk_proof_check_shape"
+
+      external check_shape_with_handle :
+           VerifierIndex.Fq.handle
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+        -> (unit, Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fq_plonk_proof_check_shape_with_handle"
+
+      external batch_verify_result :
+           ( Pasta_bindings.Fq.t
+           , SRS.Fq.t
+           , Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+           array
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+           array
+        -> (unit, int * Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fq_plonk_proof_batch_verify_result"
+
+      external batch_verify_bisect :
+           ( Pasta_bindings.Fq.t
+           , SRS.Fq.t
+           , Pasta_bindings.Fp.t Kimchi_types.or_infinity Kimchi_types.poly_comm
+           )
+           Kimchi_types.VerifierIndex.verifier_index
+           array
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+           array
+        -> int array = "caml_pasta_fq_plonk_proof_batch_verify_bisect"
+
+      external verify_with_handle :
+           VerifierIndex.Fq.handle
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+        -> bool = "caml_pasta_fq_plonk_proof_verify_with_handle"
+
+      external batch_verify_with_handle :
+           VerifierIndex.Fq.handle
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+           array
+        -> bool = "caml_pasta_fq_plonk_proof_batch_verify_with_handle"
+
+      external verify_result_with_handle :
+           VerifierIndex.Fq.handle
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+        -> (unit, Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fq_plonk_proof_verify_result_with_handle"
+
+      external batch_verify_result_with_handle :
+           VerifierIndex.Fq.handle
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+           array
+        -> (unit, int * Kimchi_types.VerifyError.t) result
+        = "caml_pasta_fq_plonk_proof_batch_verify_result_with_handle"
+
+      external to_bytes :
+           ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+        -> string = "caml_pasta_fq_plonk_proof_to_bytes"
+
+      external of_bytes :
+           string
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public = "caml_pasta_fq_plonk_proof_of_bytes"
+
+      external to_json :
+           ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+        -> string = "caml_pasta_fq_plonk_proof_to_json"
+
+      external of_json :
+           string
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public = "caml_pasta_fq_plonk_proof_of_json"
+
       external dummy :
            unit
         -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
            , Pasta_bindings.Fq.t )
            Kimchi_types.proof_with_public = "caml_pasta_fq_plonk_proof_dummy"
 
+      external dummy_with_shape :
+           Kimchi_types.DummyShape.t
+        -> int
+        -> ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+        = "caml_pasta_fq_plonk_proof_dummy_with_shape"
+
       external deep_copy :
            ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
            , Pasta_bindings.Fq.t )
@@ -565,5 +1224,24 @@ module Protocol = struct
            Kimchi_types.proof_with_public
         = "caml_pasta_fq_plonk_proof_deep_copy"
     end
+
+    external batch_verify_mixed :
+         ( VerifierIndex.Fp.t
+         , ( Pasta_bindings.Fq.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fp.t )
+           Kimchi_types.proof_with_public
+         , SRS.Fp.t
+         , Pasta_bindings.Fq.t Kimchi_types.or_infinity
+         , Pasta_bindings.Fp.t )
+         Kimchi_types.BatchVerify.input
+      -> ( VerifierIndex.Fq.t
+         , ( Pasta_bindings.Fp.t Kimchi_types.or_infinity
+           , Pasta_bindings.Fq.t )
+           Kimchi_types.proof_with_public
+         , SRS.Fq.t
+         , Pasta_bindings.Fp.t Kimchi_types.or_infinity
+         , Pasta_bindings.Fq.t )
+         Kimchi_types.BatchVerify.input
+      -> bool = "caml_pasta_plonk_proof_batch_verify_mixed"
   end
 end
@@ -202,6 +202,118 @@ type nonrec 'f oracles =
   ; digest_before_evaluations : 'f
   }
 
+type nonrec sponge = FqSponge | FrSponge
+
+type nonrec ('f, 'g, 'base_f) transcript_value =
+  | Point of 'g
+  | Scalar of 'f
+  | BaseField of 'base_f
+
+type nonrec ('f, 'g, 'base_f) transcript_entry =
+  { sponge : sponge
+  ; step : string
+  ; squeezed : bool
+  ; value : ('f, 'g, 'base_f) transcript_value
+  }
+
+type nonrec ('f, 'g, 'base_f) oracles_with_transcript =
+  { oracles : 'f oracles
+  ; transcript : ('f, 'g, 'base_f) transcript_entry array
+  }
+
+module SetupError = struct
+  type nonrec t =
+    | DomainTooLarge of (int * int)
+    | DomainCreation of string
+    | LookupTableIdCollision of int
+    | RuntimeTableMisconfigured of (int * string)
+    | WrongNumberOfPublicInputs of (int * int)
+    | Other of string
+end
+
+module Witness = struct
+  type nonrec 'f row =
+    { row : int; gate_type : gate_type; curr : 'f array; next : 'f array }
+
+  type nonrec 'f failure =
+    | Constraint of string
+    | CopyConstraint of (int * wire)
+    | MissingLookup of ('f * 'f array)
+
+  type nonrec 'f unsatisfied = { location : 'f row; failure : 'f failure }
+end
+
+type nonrec proving_phase =
+  | Queued
+  | Preparing
+  | Proving
+  | Finishing
+  | Done
+  | Cancelled
+  | Failed of string
+
+type nonrec proving_status = { phase : proving_phase; elapsed_ms : int }
+
+module ProverInput = struct
+  type nonrec ('w, 'f, 'g) t =
+    { witness : 'w array
+    ; runtime_tables : 'f runtime_table array
+    ; prev_challenges : 'f array
+    ; prev_sgs : 'g array
+    }
+end
+
+module ProverPool = struct
+  type nonrec config =
+    { name : string
+    ; num_threads : int option
+    ; stack_size : int option
+    ; max_concurrent_proofs : int option
+    }
+end
+
+module VerifyError = struct
+  type nonrec t =
+    | WrongPublicInputLength of (int * int)
+    | IncorrectCommitmentShape of string
+    | OpeningProofFailed
+    | VerifierIndexMismatch of string
+    | Other of string
+end
+
+module BatchVerify = struct
+  type nonrec ('index, 'proof, 'srs, 'g, 'f) input =
+    { indexes : 'index array
+    ; proofs : 'proof array
+    ; srs : 'srs
+    ; accumulator_comms : 'g array
+    ; accumulator_chals : 'f array
+    }
+end
+
+module DummyShape = struct
+  type nonrec feature_flags =
+    { range_check0 : bool
+    ; range_check1 : bool
+    ; foreign_field_add : bool
+    ; foreign_field_mul : bool
+    ; xor : bool
+    ; rot : bool
+    ; lookup_features : lookup_features
+    }
+
+  type nonrec t =
+    { feature_flags : feature_flags
+    ; public_input_size : int
+    ; prev_challenges : int
+    ; log2_domain_size : int
+    ; num_chunks : int
+    }
+end
+
+type nonrec verifier_index_change =
+  { field : string; before : string; after : string }
+
 module VerifierIndex = struct
   module Lookup = struct
     type nonrec lookups_used = Single | Joint
@@ -24,3 +24,22 @@ impl<'a> OCamlDesc for CamlBytesString<'a> {
         const_random!(u128)
     }
 }
+
+/// Owned bytes returned to OCaml as a string.
+pub struct CamlBytes(pub Vec<u8>);
+
+unsafe impl IntoValue for CamlBytes {
+    fn into_value(self, rt: &Runtime) -> Value {
+        self.0.as_slice().into_value(rt)
+    }
+}
+
+impl OCamlDesc for CamlBytes {
+    fn ocaml_desc(_env: &Env, _generics: &[&str]) -> String {
+        "string".to_string()
+    }
+
+    fn unique_id() -> u128 {
+        const_random!(u128)
+    }
+}
@@ -0,0 +1,33 @@
+use ocaml_gen::{const_random, Env, OCamlDesc};
+
+/// A Rust mirror of OCaml's `result` type.
+/// Functions returning `Result<_, ocaml::Error>` raise an exception on error,
+/// use this instead when the error is a value OCaml should be able to match on.
+#[derive(ocaml::IntoValue, ocaml::FromValue)]
+pub enum CamlResult<T, E> {
+    Ok(T),
+    Error(E),
+}
+
+impl<T, E> From<Result<T, E>> for CamlResult<T, E> {
+    fn from(res: Result<T, E>) -> Self {
+        match res {
+            Ok(x) => CamlResult::Ok(x),
+            Err(e) => CamlResult::Error(e),
+        }
+    }
+}
+
+impl<T: OCamlDesc, E: OCamlDesc> OCamlDesc for CamlResult<T, E> {
+    fn ocaml_desc(env: &Env, generics: &[&str]) -> String {
+        format!(
+            "({}, {}) result",
+            T::ocaml_desc(env, generics),
+            E::ocaml_desc(env, generics)
+        )
+    }
+
+    fn unique_id() -> u128 {
+        const_random!(u128)
+    }
+}
@@ -1,4 +1,5 @@
 pub mod caml_bytes_string;
+pub mod caml_result;
 
 #[macro_use]
 pub mod caml_pointer;
@@ -0,0 +1,385 @@
+//! Dummy proofs and verifier indexes with the shape of a given circuit,
+//! used to pad Pickles and in tests of circuits with custom gates or lookups.
+
+use crate::plonk_verifier_index::{
+    CamlLookupInfo, CamlLookupSelectors, CamlLookupVerifierIndex, CamlPlonkDomain,
+    CamlPlonkVerificationEvals, CamlPlonkVerifierIndex,
+};
+use crate::proof_shape::QUOTIENT_PIECES;
+use ark_ec::AffineCurve;
+use ark_ff::One;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain as Domain};
+use kimchi::{
+    circuits::{
+        constraints::FeatureFlags,
+        lookup::lookups::{LookupFeatures, LookupInfo},
+        polynomials::permutation::Shifts,
+        wires::{COLUMNS, PERMUTS},
+    },
+    curve::KimchiCurve,
+    proof::{
+        LookupCommitments, PointEvaluations, ProofEvaluations, ProverCommitments, ProverProof,
+        RecursionChallenge,
+    },
+};
+use poly_commitment::{
+    commitment::{caml::CamlPolyComm, PolyComm},
+    evaluation_proof::OpeningProof,
+};
+
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlFeatureFlags {
+    pub range_check0: bool,
+    pub range_check1: bool,
+    pub foreign_field_add: bool,
+    pub foreign_field_mul: bool,
+    pub xor: bool,
+    pub rot: bool,
+    pub lookup_features: LookupFeatures,
+}
+
+impl From<CamlFeatureFlags> for FeatureFlags {
+    fn from(flags: CamlFeatureFlags) -> Self {
+        let CamlFeatureFlags {
+            range_check0,
+            range_check1,
+            foreign_field_add,
+            foreign_field_mul,
+            xor,
+            rot,
+            lookup_features,
+        } = flags;
+        FeatureFlags {
+            range_check0,
+            range_check1,
+            foreign_field_add,
+            foreign_field_mul,
+            xor,
+            rot,
+            lookup_features,
+        }
+    }
+}
+
+/// The shape of the circuit that dummies should match.
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlDummyShape {
+    pub feature_flags: CamlFeatureFlags,
+    pub public_input_size: ocaml::Int,
+    pub prev_challenges: ocaml::Int,
+    pub log2_domain_size: ocaml::Int,
+    /// The number of chunks of each polynomial, a power of two
+    pub num_chunks: ocaml::Int,
+}
+
+/// A validated [CamlDummyShape].
+pub struct DummyShape {
+    pub feature_flags: FeatureFlags,
+    pub public_input_size: usize,
+    pub prev_challenges: usize,
+    pub log2_domain_size: usize,
+    pub num_chunks: usize,
+    /// `None` if the circuit does not use lookups
+    pub lookup_info: Option<LookupInfo>,
+}
+
+impl TryFrom<CamlDummyShape> for DummyShape {
+    type Error = ocaml::Error;
+
+    fn try_from(shape: CamlDummyShape) -> Result<Self, Self::Error> {
+        let invalid = |name| ocaml::Error::invalid_argument(name).err().unwrap();
+        let public_input_size =
+            usize::try_from(shape.public_input_size).map_err(|_| invalid("public_input_size"))?;
+        let prev_challenges =
+            usize::try_from(shape.prev_challenges).map_err(|_| invalid("prev_challenges"))?;
+        let log2_domain_size = match usize::try_from(shape.log2_domain_size) {
+            Ok(log2) if (1..32).contains(&log2) => log2,
+            _ => return Err(invalid("log2_domain_size")),
+        };
+        let num_chunks = match usize::try_from(shape.num_chunks) {
+            Ok(n) if n.is_power_of_two() && n < 1 << log2_domain_size => n,
+            _ => return Err(invalid("num_chunks")),
+        };
+
+        let feature_flags: FeatureFlags = shape.feature_flags.into();
+        let lookup_info = lookup_info(feature_flags.lookup_features);
+
+        Ok(DummyShape {
+            feature_flags,
+            public_input_size,
+            prev_challenges,
+            log2_domain_size,
+            num_chunks,
+            lookup_info,
+        })
+    }
+}
+
+/// The lookup configuration of a circuit with `features`, or `None` if it does not use lookups.
+fn lookup_info(features: LookupFeatures) -> Option<LookupInfo> {
+    let uses_lookups = features.patterns.xor
+        || features.patterns.lookup
+        || features.patterns.range_check
+        || features.patterns.foreign_field_mul
+        || features.uses_runtime_tables;
+    uses_lookups.then(|| LookupInfo::create(features))
+}
+
+impl DummyShape {
+    /// A small shape for tests: 3 public inputs, 2 previous challenges,
+    /// and a domain of 32 rows in a single chunk.
+    #[cfg(test)]
+    pub(crate) fn for_tests(feature_flags: FeatureFlags) -> Self {
+        DummyShape {
+            lookup_info: lookup_info(feature_flags.lookup_features),
+            feature_flags,
+            public_input_size: 3,
+            prev_challenges: 2,
+            log2_domain_size: 5,
+            num_chunks: 1,
+        }
+    }
+
+    pub fn max_poly_size(&self) -> usize {
+        (1 << self.log2_domain_size) / self.num_chunks
+    }
+
+    /// The number of rounds of an opening proof, which halve the SRS down to a point.
+    pub fn opening_rounds(&self) -> usize {
+        self.max_poly_size().trailing_zeros() as usize
+    }
+
+    /// The number of rows reserved for zero-knowledge, as chosen by kimchi's constraint system.
+    pub fn zk_rows(&self) -> usize {
+        let lower_bound = (2 * (PERMUTS + 1) * self.num_chunks - 2) / PERMUTS;
+        std::cmp::max(3, lower_bound + 1)
+    }
+
+    fn num_sorted(&self) -> usize {
+        self.lookup_info
+            .as_ref()
+            .map(|info| info.max_per_row + 1)
+            .unwrap_or(0)
+    }
+
+    /// The commitments of a verifier index, all set to `comm()`.
+    pub fn verification_evals<C>(&self, comm: impl Fn() -> C) -> CamlPlonkVerificationEvals<C> {
+        let flags = &self.feature_flags;
+        let comm_if = |enabled: bool| enabled.then(&comm);
+        CamlPlonkVerificationEvals {
+            sigma_comm: (0..PERMUTS).map(|_| comm()).collect(),
+            coefficients_comm: (0..COLUMNS).map(|_| comm()).collect(),
+            generic_comm: comm(),
+            psm_comm: comm(),
+            complete_add_comm: comm(),
+            mul_comm: comm(),
+            emul_comm: comm(),
+            endomul_scalar_comm: comm(),
+            xor_comm: comm_if(flags.xor),
+            range_check0_comm: comm_if(flags.range_check0),
+            range_check1_comm: comm_if(flags.range_check1),
+            foreign_field_add_comm: comm_if(flags.foreign_field_add),
+            foreign_field_mul_comm: comm_if(flags.foreign_field_mul),
+            rot_comm: comm_if(flags.rot),
+        }
+    }
+
+    /// The lookup part of a verifier index, with its commitments set to `comm()`.
+    pub fn lookup_index<C>(&self, comm: impl Fn() -> C) -> Option<CamlLookupVerifierIndex<C>> {
+        let info = self.lookup_info.as_ref()?;
+        let features = &info.features;
+        let comm_if = |enabled: bool| enabled.then(&comm);
+        Some(CamlLookupVerifierIndex {
+            joint_lookup_used: features.joint_lookup_used,
+            lookup_table: (0..std::cmp::max(1, info.max_joint_size))
+                .map(|_| comm())
+                .collect(),
+            lookup_selectors: CamlLookupSelectors {
+                lookup: comm_if(features.patterns.lookup),
+                xor: comm_if(features.patterns.xor),
+                range_check: comm_if(features.patterns.range_check),
+                ffmul: comm_if(features.patterns.foreign_field_mul),
+            },
+            table_ids: comm_if(features.joint_lookup_used),
+            lookup_info: CamlLookupInfo {
+                max_per_row: info.max_per_row as ocaml::Int,
+                max_joint_size: info.max_joint_size as ocaml::Int,
+                features: info.features,
+            },
+            runtime_tables_selector: comm_if(features.uses_runtime_tables),
+        })
+    }
+
+    /// A verifier index with the dimensions of this shape, and all its commitments set to
+    /// the generator. `srs` should have `max_poly_size` points for the opening proofs to
+    /// have the right size.
+    pub fn verifier_index<G, CamlF, CamlG, Srs>(
+        &self,
+        srs: Srs,
+    ) -> CamlPlonkVerifierIndex<CamlF, Srs, CamlPolyComm<CamlG>>
+    where
+        G: KimchiCurve,
+        CamlF: From<G::ScalarField>,
+        CamlG: From<G> + Clone,
+    {
+        let g: CamlG = G::prime_subgroup_generator().into();
+        let comm = || CamlPolyComm {
+            shifted: None,
+            unshifted: vec![g.clone(); self.num_chunks],
+        };
+        let domain = Domain::<G::ScalarField>::new(1 << self.log2_domain_size).unwrap();
+
+        CamlPlonkVerifierIndex {
+            domain: CamlPlonkDomain {
+                log_size_of_group: self.log2_domain_size as isize,
+                group_gen: domain.group_gen.into(),
+            },
+            max_poly_size: self.max_poly_size() as isize,
+            public: self.public_input_size as isize,
+            prev_challenges: self.prev_challenges as isize,
+            srs,
+            evals: self.verification_evals(comm),
+            shifts: Shifts::new(&domain)
+                .shifts()
+                .iter()
+                .map(|shift| (*shift).into())
+                .collect(),
+            lookup_index: self.lookup_index(comm),
+            zk_rows: self.zk_rows() as isize,
+        }
+    }
+
+    /// A proof with the dimensions of this shape, and a public input of ones.
+    /// `prev_challenge_rounds` is the number of rounds of the opening proofs of the other
+    /// curve, whose challenges the previous challenges are.
+    pub fn proof<G: KimchiCurve>(
+        &self,
+        prev_challenge_rounds: usize,
+    ) -> (ProverProof<G, OpeningProof<G>>, Vec<G::ScalarField>) {
+        let g = G::prime_subgroup_generator();
+        let one = G::ScalarField::one();
+        let chunks = self.num_chunks;
+        let comm = |n: usize| PolyComm {
+            shifted: None,
+            unshifted: vec![g; n],
+        };
+        let eval = || PointEvaluations {
+            zeta: vec![one; chunks],
+            zeta_omega: vec![one; chunks],
+        };
+        let eval_if = |enabled: bool| enabled.then(eval);
+
+        let flags = &self.feature_flags;
+        let features = &flags.lookup_features;
+        let uses_lookups = self.lookup_info.is_some();
+        let num_sorted = self.num_sorted();
+
+        let commitments = ProverCommitments {
+            w_comm: std::array::from_fn(|_| comm(chunks)),
+            z_comm: comm(chunks),
+            t_comm: comm(QUOTIENT_PIECES * chunks),
+            lookup: uses_lookups.then(|| LookupCommitments {
+                sorted: (0..num_sorted).map(|_| comm(chunks)).collect(),
+                aggreg: comm(chunks),
+                runtime: features.uses_runtime_tables.then(|| comm(chunks)),
+            }),
+        };
+
+        let evals = ProofEvaluations {
+            public: Some(eval()),
+            w: std::array::from_fn(|_| eval()),
+            coefficients: std::array::from_fn(|_| eval()),
+            z: eval(),
+            s: std::array::from_fn(|_| eval()),
+            generic_selector: eval(),
+            poseidon_selector: eval(),
+            complete_add_selector: eval(),
+            mul_selector: eval(),
+            emul_selector: eval(),
+            endomul_scalar_selector: eval(),
+            range_check0_selector: eval_if(flags.range_check0),
+            range_check1_selector: eval_if(flags.range_check1),
+            foreign_field_add_selector: eval_if(flags.foreign_field_add),
+            foreign_field_mul_selector: eval_if(flags.foreign_field_mul),
+            xor_selector: eval_if(flags.xor),
+            rot_selector: eval_if(flags.rot),
+            lookup_aggregation: eval_if(uses_lookups),
+            lookup_table: eval_if(uses_lookups),
+            lookup_sorted: std::array::from_fn(|i| eval_if(i < num_sorted)),
+            runtime_lookup_table: eval_if(features.uses_runtime_tables),
+            runtime_lookup_table_selector: eval_if(features.uses_runtime_tables),
+            xor_lookup_selector: eval_if(features.patterns.xor),
+            lookup_gate_lookup_selector: eval_if(features.patterns.lookup),
+            range_check_lookup_selector: eval_if(features.patterns.range_check),
+            foreign_field_mul_lookup_selector: eval_if(features.patterns.foreign_field_mul),
+        };
+
+        let rounds = self.opening_rounds();
+        // the commitment to the challenge polynomial of a previous proof is a single point
+        let prev = RecursionChallenge {
+            chals: vec![one; prev_challenge_rounds],
+            comm: comm(1),
+        };
+        let proof = ProverProof {
+            commitments,
+            proof: OpeningProof {
+                lr: vec![(g, g); rounds],
+                z1: one,
+                z2: one,
+                delta: g,
+                sg: g,
+            },
+            evals,
+            ft_eval1: one,
+            prev_challenges: vec![prev; self.prev_challenges],
+        };
+        (proof, vec![one; self.public_input_size])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pasta_fp_plonk_verifier_index::CamlPastaFpPlonkVerifierIndex;
+    use crate::proof_shape::check_proof_shape;
+    use crate::srs::fp::CamlFpSrs;
+    use kimchi::{circuits::lookup::lookups::LookupPatterns, verifier_index::VerifierIndex};
+    use mina_curves::pasta::Vesta;
+    use poly_commitment::srs::SRS;
+
+    #[test]
+    fn dummy_proof_matches_dummy_verifier_index() {
+        let lookup_features = LookupFeatures {
+            patterns: LookupPatterns {
+                xor: true,
+                lookup: false,
+                range_check: true,
+                foreign_field_mul: false,
+            },
+            joint_lookup_used: true,
+            uses_runtime_tables: true,
+        };
+        let feature_flags = FeatureFlags {
+            range_check0: true,
+            range_check1: true,
+            foreign_field_add: false,
+            foreign_field_mul: false,
+            xor: true,
+            rot: false,
+            lookup_features,
+        };
+        for num_chunks in [1, 2] {
+            let shape = DummyShape {
+                num_chunks,
+                ..DummyShape::for_tests(feature_flags)
+            };
+
+            let srs = CamlFpSrs::new(SRS::create(shape.max_poly_size()));
+            let index: CamlPastaFpPlonkVerifierIndex = shape.verifier_index::<Vesta, _, _, _>(srs);
+            let index: VerifierIndex<Vesta, OpeningProof<Vesta>> = index.into();
+            let (proof, public_input) = shape.proof::<Vesta>(15);
+
+            assert!(check_proof_shape(&index, &proof, public_input.len()).is_ok());
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Helpers for the byte encodings of proofs and verifier indexes.
+//!
+//! Every encoding starts with a header: four magic bytes naming what is encoded,
+//! a version byte, and the name of the field of the circuit (`fp` or `fq`).
+//! Decoding is strict: it fails on a wrong header, on malformed values and on trailing bytes.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+/// Appends the header of an encoding to `bytes`.
+pub fn encode_header(bytes: &mut Vec<u8>, magic: &[u8; 4], version: u8, tag: &str) {
+    bytes.extend_from_slice(magic);
+    bytes.push(version);
+    bytes.extend_from_slice(tag.as_bytes());
+}
+
+/// Checks the header written by [encode_header], and returns the bytes that follow it.
+pub fn decode_header<'a>(
+    bytes: &'a [u8],
+    magic: &[u8; 4],
+    version: u8,
+    tag: &str,
+) -> Result<&'a [u8], &'static str> {
+    let header_len = magic.len() + 1 + tag.len();
+    if bytes.len() < header_len || &bytes[..magic.len()] != magic {
+        return Err("unexpected magic bytes");
+    }
+    if bytes[magic.len()] != version {
+        return Err("unsupported encoding version");
+    }
+    if &bytes[magic.len() + 1..header_len] != tag.as_bytes() {
+        return Err("encoded for the other curve");
+    }
+    Ok(&bytes[header_len..])
+}
+
+/// Appends explicit fields to an encoding: integers are little-endian,
+/// points and field elements use arkworks' canonical (compressed) serialization.
+pub struct Writer(pub Vec<u8>);
+
+impl Writer {
+    pub fn u8(&mut self, x: u8) {
+        self.0.push(x)
+    }
+
+    pub fn bool(&mut self, x: bool) {
+        self.u8(x as u8)
+    }
+
+    pub fn u32(&mut self, x: u32) {
+        self.0.extend_from_slice(&x.to_le_bytes())
+    }
+
+    pub fn u64(&mut self, x: u64) {
+        self.0.extend_from_slice(&x.to_le_bytes())
+    }
+
+    pub fn length(&mut self, len: usize) -> Result<(), &'static str> {
+        let len: u32 = len.try_into().map_err(|_| "too many elements to encode")?;
+        self.u32(len);
+        Ok(())
+    }
+
+    pub fn canonical<T: CanonicalSerialize>(&mut self, x: &T) -> Result<(), &'static str> {
+        x.serialize(&mut self.0)
+            .map_err(|_| "could not serialize a point or field element")
+    }
+}
+
+/// Reads the fields written by [Writer], rejecting the values that it never writes.
+pub struct Reader<'a>(pub &'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], &'static str> {
+        if self.0.len() < n {
+            return Err("unexpected end of the bytes");
+        }
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, &'static str> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn bool(&mut self) -> Result<bool, &'static str> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err("a boolean is neither 0 nor 1"),
+        }
+    }
+
+    pub fn u32(&mut self) -> Result<u32, &'static str> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, &'static str> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub fn length(&mut self) -> Result<usize, &'static str> {
+        Ok(self.u32()? as usize)
+    }
+
+    /// Fails if the point is not on the curve or in its subgroup, or if the field element
+    /// is not canonical.
+    pub fn canonical<T: CanonicalDeserialize>(&mut self) -> Result<T, &'static str> {
+        T::deserialize(&mut self.0).map_err(|_| "invalid point or field element")
+    }
+
+    /// Fails if any bytes remain.
+    pub fn finish(self) -> Result<(), &'static str> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err("trailing bytes")
+        }
+    }
+}
@@ -1,10 +1,27 @@
 //! A GateVector: this is used to represent a list of gates.
 
+use ark_ff::PrimeField;
 use kimchi::circuits::{
     gate::{caml::CamlCircuitGate, Circuit, CircuitGate},
     wires::caml::CamlWire,
 };
 use o1_utils::hasher::CryptoDigest;
+use serde::Deserialize;
+
+/// An owned counterpart of [Circuit], used to read back the JSON produced by
+/// `caml_pasta_*_plonk_circuit_serialize` (which borrows its gates).
+#[derive(Deserialize)]
+#[serde(bound = "CircuitGate<F>: Deserialize<'de>")]
+pub struct SerializedCircuit<F: PrimeField> {
+    pub public_input_size: usize,
+    pub gates: Vec<CircuitGate<F>>,
+}
+
+impl<F: PrimeField> SerializedCircuit<F> {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
 
 // TODO: get rid of this
 
@@ -101,6 +118,22 @@ pub mod fp {
         let circuit = Circuit::new(usize::try_from(public_input_size).unwrap(), &v.as_ref().0);
         serde_json::to_string(&circuit).expect("couldn't serialize constraints")
     }
+
+    #[ocaml_gen::func]
+    #[ocaml::func]
+    pub fn caml_pasta_fp_plonk_circuit_deserialize(
+        json: String,
+    ) -> Result<(ocaml::Int, CamlPastaFpPlonkGateVector), ocaml::Error> {
+        let circuit = SerializedCircuit::<Fp>::from_json(&json).map_err(|_| {
+            ocaml::Error::invalid_argument("caml_pasta_fp_plonk_circuit_deserialize")
+                .err()
+                .unwrap()
+        })?;
+        Ok((
+            circuit.public_input_size as isize,
+            CamlPastaFpPlonkGateVector(circuit.gates),
+        ))
+    }
 }
 
 //
@@ -194,4 +227,52 @@ pub mod fq {
         let circuit = Circuit::new(usize::try_from(public_input_size).unwrap(), &v.as_ref().0);
         serde_json::to_string(&circuit).expect("couldn't serialize constraints")
     }
+
+    #[ocaml_gen::func]
+    #[ocaml::func]
+    pub fn caml_pasta_fq_plonk_circuit_deserialize(
+        json: String,
+    ) -> Result<(ocaml::Int, CamlPastaFqPlonkGateVector), ocaml::Error> {
+        let circuit = SerializedCircuit::<Fq>::from_json(&json).map_err(|_| {
+            ocaml::Error::invalid_argument("caml_pasta_fq_plonk_circuit_deserialize")
+                .err()
+                .unwrap()
+        })?;
+        Ok((
+            circuit.public_input_size as isize,
+            CamlPastaFqPlonkGateVector(circuit.gates),
+        ))
+    }
+}
+
+//
+// Tests
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kimchi::circuits::{polynomials::generic::GenericGateSpec, wires::Wire};
+    use mina_curves::pasta::Fp;
+
+    #[test]
+    fn circuit_json_roundtrip() {
+        let gates: Vec<_> = (0..4)
+            .map(|row| {
+                CircuitGate::<Fp>::create_generic_gadget(
+                    Wire::for_row(row),
+                    GenericGateSpec::Const(Fp::from(row as u64)),
+                    None,
+                )
+            })
+            .collect();
+        let json = serde_json::to_string(&Circuit::new(1, &gates)).unwrap();
+
+        let circuit = SerializedCircuit::<Fp>::from_json(&json).unwrap();
+        assert_eq!(circuit.public_input_size, 1);
+        assert_eq!(
+            Circuit::new(1, &circuit.gates).digest(),
+            Circuit::new(1, &gates).digest()
+        );
+    }
 }
@@ -19,6 +19,9 @@ pub mod arkworks;
 /// Utils
 pub mod urs_utils; // TODO: move this logic to proof-systems
 
+/// Byte encodings
+pub mod encoding;
+
 /// Vectors
 pub mod field_vector;
 pub mod gate_vector;
@@ -32,19 +35,31 @@ pub mod srs;
 /// Indexes
 pub mod pasta_fp_plonk_index;
 pub mod pasta_fq_plonk_index;
+pub mod setup_error;
 
 /// Verifier indexes/keys
 pub mod plonk_verifier_index;
 
 pub mod pasta_fp_plonk_verifier_index;
 pub mod pasta_fq_plonk_verifier_index;
+pub mod verifier_index_diff;
 
 /// Oracles
 pub mod oracles;
+pub mod transcript;
 
 /// Proofs
+#[macro_use]
+pub mod plonk_proof;
 pub mod pasta_fp_plonk_proof;
 pub mod pasta_fq_plonk_proof;
+pub mod dummy;
+pub mod mixed_batch_verify;
+pub mod proof_shape;
+pub mod prover_pool;
+pub mod proving_task;
+pub mod verify_error;
+pub mod witness;
 
 /// Poseidon
 pub mod pasta_fp_poseidon;
@@ -33,8 +33,16 @@ pub fn linearization_strings<F: ark_ff::PrimeField + ark_ff::SquareRootField>(
             },
         })
     };
-    let evaluated_cols = linearization_columns::<F>(features.as_ref());
-    let (linearization, _powers_of_alpha) = constraints_expr::<F>(features.as_ref(), true);
+    feature_linearization_strings::<F>(features.as_ref())
+}
+
+/// Same as [linearization_strings], for the circuits with the given features,
+/// or for all of them if `None`.
+pub fn feature_linearization_strings<F: ark_ff::PrimeField + ark_ff::SquareRootField>(
+    features: Option<&FeatureFlags>,
+) -> (String, Vec<(String, String)>) {
+    let evaluated_cols = linearization_columns::<F>(features);
+    let (linearization, _powers_of_alpha) = constraints_expr::<F>(features, true);
 
     let Linearization {
         constant_term,
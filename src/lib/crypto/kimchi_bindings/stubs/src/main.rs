@@ -13,8 +13,10 @@ use std::io::Write;
 use wires_15_stubs::{
     // we must import all here, to have access to the derived functions
     arkworks::{bigint_256::*, group_affine::*, group_projective::*, pasta_fp::*, pasta_fq::*},
+    dummy::{CamlDummyShape, CamlFeatureFlags},
     field_vector::{fp::*, fq::*},
     gate_vector::{fp::*, fq::*},
+    mixed_batch_verify::{caml_pasta_plonk_proof_batch_verify_mixed, CamlBatchVerifyInput},
     oracles::{fp::*, fq::*, CamlOracles},
     pasta_fp_plonk_index::*,
     pasta_fp_plonk_proof::*,
@@ -22,12 +24,26 @@ use wires_15_stubs::{
     pasta_fq_plonk_index::*,
     pasta_fq_plonk_proof::*,
     pasta_fq_plonk_verifier_index::*,
+    plonk_proof::CamlProverInput,
     plonk_verifier_index::{
         CamlLookupInfo, CamlLookupSelectors, CamlLookupVerifierIndex, CamlLookupsUsed,
         CamlPlonkDomain, CamlPlonkVerificationEvals, CamlPlonkVerifierIndex,
     },
     projective::{pallas::*, vesta::*},
+    prover_pool::{
+        caml_prover_pool_configure, caml_prover_pool_num_threads, caml_prover_pool_select,
+        CamlProverPoolConfig,
+    },
+    proving_task::{
+        caml_proving_task_cancel, caml_proving_task_create, caml_proving_task_status,
+        CamlProvingPhase, CamlProvingStatus, CamlProvingTask,
+    },
+    setup_error::CamlSetupError,
     srs::{fp::*, fq::*},
+    transcript::{CamlOraclesWithTranscript, CamlSponge, CamlTranscriptEntry, CamlTranscriptValue},
+    verifier_index_diff::CamlVerifierIndexChange,
+    verify_error::CamlVerifyError,
+    witness::{CamlUnsatisfiedWitness, CamlWitnessFailure, CamlWitnessRow},
     CamlCircuitGate,
     CamlLookupCommitments,
     CamlOpeningProof,
@@ -95,6 +111,8 @@ fn generate_types_bindings(mut w: impl std::io::Write, env: &mut Env) {
     decl_fake_generic!(T1, 0);
     decl_fake_generic!(T2, 1);
     decl_fake_generic!(T3, 2);
+    decl_fake_generic!(T4, 3);
+    decl_fake_generic!(T5, 4);
 
     decl_type!(w, env, CamlGroupAffine<T1> => "or_infinity");
     decl_type!(w, env, CamlScalarChallenge::<T1> => "scalar_challenge");
@@ -124,6 +142,37 @@ fn generate_types_bindings(mut w: impl std::io::Write, env: &mut Env) {
     decl_type!(w, env, CurrOrNext => "curr_or_next");
 
     decl_type!(w, env, CamlOracles<T1> => "oracles");
+    decl_type!(w, env, CamlSponge => "sponge");
+    decl_type!(w, env, CamlTranscriptValue<T1, T2, T3> => "transcript_value");
+    decl_type!(w, env, CamlTranscriptEntry<T1, T2, T3> => "transcript_entry");
+    decl_type!(w, env, CamlOraclesWithTranscript<T1, T2, T3> => "oracles_with_transcript");
+    decl_module!(w, env, "SetupError", {
+        decl_type!(w, env, CamlSetupError => "t");
+    });
+    decl_module!(w, env, "Witness", {
+        decl_type!(w, env, CamlWitnessRow<T1> => "row");
+        decl_type!(w, env, CamlWitnessFailure<T1> => "failure");
+        decl_type!(w, env, CamlUnsatisfiedWitness<T1> => "unsatisfied");
+    });
+    decl_type!(w, env, CamlProvingPhase => "proving_phase");
+    decl_type!(w, env, CamlProvingStatus => "proving_status");
+    decl_module!(w, env, "ProverInput", {
+        decl_type!(w, env, CamlProverInput<T1, T2, T3> => "t");
+    });
+    decl_module!(w, env, "ProverPool", {
+        decl_type!(w, env, CamlProverPoolConfig => "config");
+    });
+    decl_module!(w, env, "VerifyError", {
+        decl_type!(w, env, CamlVerifyError => "t");
+    });
+    decl_module!(w, env, "BatchVerify", {
+        decl_type!(w, env, CamlBatchVerifyInput<T1, T2, T3, T4, T5> => "input");
+    });
+    decl_module!(w, env, "DummyShape", {
+        decl_type!(w, env, CamlFeatureFlags => "feature_flags");
+        decl_type!(w, env, CamlDummyShape => "t");
+    });
+    decl_type!(w, env, CamlVerifierIndexChange => "verifier_index_change");
     decl_module!(w, env, "VerifierIndex", {
         decl_module!(w, env, "Lookup", {
             decl_type!(w, env, CamlLookupsUsed => "lookups_used");
@@ -335,6 +384,7 @@ fn generate_kimchi_bindings(mut w: impl std::io::Write, env: &mut Env) {
                     decl_func!(w, env, caml_pasta_fp_plonk_gate_vector_wrap => "wrap");
                     decl_func!(w, env, caml_pasta_fp_plonk_gate_vector_digest => "digest");
                     decl_func!(w, env, caml_pasta_fp_plonk_circuit_serialize => "to_json");
+                    decl_func!(w, env, caml_pasta_fp_plonk_circuit_deserialize => "of_json");
                 });
                 decl_module!(w, env, "Fq", {
                     decl_type!(w, env, CamlPastaFqPlonkGateVector => "t");
@@ -347,6 +397,7 @@ fn generate_kimchi_bindings(mut w: impl std::io::Write, env: &mut Env) {
                     decl_func!(w, env, caml_pasta_fq_plonk_gate_vector_wrap => "wrap");
                     decl_func!(w, env, caml_pasta_fq_plonk_gate_vector_digest => "digest");
                     decl_func!(w, env, caml_pasta_fq_plonk_circuit_serialize => "to_json");
+                    decl_func!(w, env, caml_pasta_fq_plonk_circuit_deserialize => "of_json");
                 });
             });
         });
@@ -392,6 +443,8 @@ fn generate_kimchi_bindings(mut w: impl std::io::Write, env: &mut Env) {
                 decl_type!(w, env, CamlPastaFpPlonkIndex => "t");
 
                 decl_func!(w, env, caml_pasta_fp_plonk_index_create => "create");
+                decl_func!(w, env, caml_pasta_fp_plonk_index_try_create => "try_create");
+                decl_func!(w, env, caml_pasta_fp_plonk_index_create_from_circuit_json => "create_from_circuit_json");
                 decl_func!(w, env, caml_pasta_fp_plonk_index_max_degree => "max_degree");
                 decl_func!(w, env, caml_pasta_fp_plonk_index_public_inputs => "public_inputs");
                 decl_func!(w, env, caml_pasta_fp_plonk_index_domain_d1_size => "domain_d1_size");
@@ -405,6 +458,8 @@ fn generate_kimchi_bindings(mut w: impl std::io::Write, env: &mut Env) {
                 decl_type!(w, env, CamlPastaFqPlonkIndex => "t");
 
                 decl_func!(w, env, caml_pasta_fq_plonk_index_create => "create");
+                decl_func!(w, env, caml_pasta_fq_plonk_index_try_create => "try_create");
+                decl_func!(w, env, caml_pasta_fq_plonk_index_create_from_circuit_json => "create_from_circuit_json");
                 decl_func!(w, env, caml_pasta_fq_plonk_index_max_degree => "max_degree");
                 decl_func!(w, env, caml_pasta_fq_plonk_index_public_inputs => "public_inputs");
                 decl_func!(w, env, caml_pasta_fq_plonk_index_domain_d1_size => "domain_d1_size");
@@ -419,22 +474,48 @@ fn generate_kimchi_bindings(mut w: impl std::io::Write, env: &mut Env) {
             decl_module!(w, env, "Fp", {
                 decl_type_alias!(w, env, "t" => CamlPlonkVerifierIndex<CamlFp, CamlFpSrs, CamlPolyComm<CamlGVesta>>);
 
+                decl_type!(w, env, CamlPastaFpPlonkVerifierIndexHandle => "handle");
+
                 decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_create => "create");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_create_handle => "create_handle");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_handle_of_index => "handle_of_index");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_handle_to_index => "handle_to_index");
                 decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_read => "read");
                 decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_write => "write");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_to_bytes => "to_bytes");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_of_bytes => "of_bytes");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_to_json => "to_json");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_of_json => "of_json");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_digest => "digest");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_bytes_digest => "bytes_digest");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_diff => "diff");
                 decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_shifts => "shifts");
                 decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_dummy => "dummy");
+                decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_dummy_with_shape => "dummy_with_shape");
                 decl_func!(w, env, caml_pasta_fp_plonk_verifier_index_deep_copy => "deep_copy");
             });
 
             decl_module!(w, env, "Fq", {
                 decl_type_alias!(w, env, "t" => CamlPlonkVerifierIndex<CamlFq, CamlFqSrs, CamlPolyComm<CamlGPallas>>);
 
+                decl_type!(w, env, CamlPastaFqPlonkVerifierIndexHandle => "handle");
+
                 decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_create => "create");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_create_handle => "create_handle");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_handle_of_index => "handle_of_index");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_handle_to_index => "handle_to_index");
                 decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_read => "read");
                 decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_write => "write");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_to_bytes => "to_bytes");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_of_bytes => "of_bytes");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_to_json => "to_json");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_of_json => "of_json");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_digest => "digest");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_bytes_digest => "bytes_digest");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_diff => "diff");
                 decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_shifts => "shifts");
                 decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_dummy => "dummy");
+                decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_dummy_with_shape => "dummy_with_shape");
                 decl_func!(w, env, caml_pasta_fq_plonk_verifier_index_deep_copy => "deep_copy");
             });
         });
@@ -445,6 +526,10 @@ fn generate_kimchi_bindings(mut w: impl std::io::Write, env: &mut Env) {
 
                 decl_func!(w, env, fp_oracles_create_no_public => "create");
                 decl_func!(w, env, fp_oracles_create => "create_with_public_evals");
+                decl_func!(w, env, fp_oracles_create_with_public => "create_with_public");
+                decl_func!(w, env, fp_oracles_create_with_handle => "create_with_handle");
+                decl_func!(w, env, fp_oracles_create_batch => "create_batch");
+                decl_func!(w, env, fp_oracles_create_debug => "create_debug");
                 decl_func!(w, env, fp_oracles_dummy => "dummy");
                 decl_func!(w, env, fp_oracles_deep_copy => "deep_copy");
             });
@@ -454,14 +539,35 @@ fn generate_kimchi_bindings(mut w: impl std::io::Write, env: &mut Env) {
 
                 decl_func!(w, env, fq_oracles_create_no_public => "create");
                 decl_func!(w, env, fq_oracles_create => "create_with_public_evals");
+                decl_func!(w, env, fq_oracles_create_with_public => "create_with_public");
+                decl_func!(w, env, fq_oracles_create_with_handle => "create_with_handle");
+                decl_func!(w, env, fq_oracles_create_batch => "create_batch");
+                decl_func!(w, env, fq_oracles_create_debug => "create_debug");
                 decl_func!(w, env, fq_oracles_dummy => "dummy");
                 decl_func!(w, env, fq_oracles_deep_copy => "deep_copy");
             });
         });
 
+        decl_module!(w, env, "ProverPool", {
+            decl_func!(w, env, caml_prover_pool_configure => "configure");
+            decl_func!(w, env, caml_prover_pool_select => "select");
+            decl_func!(w, env, caml_prover_pool_num_threads => "num_threads");
+        });
+
+        decl_module!(w, env, "ProvingTask", {
+            decl_type!(w, env, CamlProvingTask => "t");
+            decl_func!(w, env, caml_proving_task_create => "create");
+            decl_func!(w, env, caml_proving_task_cancel => "cancel");
+            decl_func!(w, env, caml_proving_task_status => "status");
+        });
+
         decl_module!(w, env, "Proof", {
             decl_module!(w, env, "Fp", {
                 decl_func!(w, env, caml_pasta_fp_plonk_proof_create => "create");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_create_owned => "create_owned");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_create_debug => "create_debug");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_create_with_task => "create_with_task");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_create_batch => "create_batch");
                 decl_func!(w, env, caml_pasta_fp_plonk_proof_create_and_verify => "create_and_verify");
                 decl_func!(w, env, caml_pasta_fp_plonk_proof_example_with_lookup => "example_with_lookup");
                 decl_func!(w, env, caml_pasta_fp_plonk_proof_example_with_ffadd => "example_with_ffadd");
@@ -472,17 +578,59 @@ fn generate_kimchi_bindings(mut w: impl std::io::Write, env: &mut Env) {
                 decl_func!(w, env, caml_pasta_fp_plonk_proof_example_with_range_check0 => "example_with_range_check0");
                 decl_func!(w, env, caml_pasta_fp_plonk_proof_verify => "verify");
                 decl_func!(w, env, caml_pasta_fp_plonk_proof_batch_verify => "batch_verify");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_verify_result => "verify_result");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_check_shape => "check_shape");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_check_shape_with_handle => "check_shape_with_handle");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_batch_verify_result => "batch_verify_result");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_batch_verify_bisect => "batch_verify_bisect");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_verify_with_handle => "verify_with_handle");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_batch_verify_with_handle => "batch_verify_with_handle");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_verify_result_with_handle => "verify_result_with_handle");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_batch_verify_result_with_handle => "batch_verify_result_with_handle");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_to_bytes => "to_bytes");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_of_bytes => "of_bytes");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_to_json => "to_json");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_of_json => "of_json");
                 decl_func!(w, env, caml_pasta_fp_plonk_proof_dummy => "dummy");
+                decl_func!(w, env, caml_pasta_fp_plonk_proof_dummy_with_shape => "dummy_with_shape");
                 decl_func!(w, env, caml_pasta_fp_plonk_proof_deep_copy => "deep_copy");
             });
 
             decl_module!(w, env, "Fq", {
                 decl_func!(w, env, caml_pasta_fq_plonk_proof_create => "create");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_create_owned => "create_owned");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_create_debug => "create_debug");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_create_with_task => "create_with_task");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_create_batch => "create_batch");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_create_and_verify => "create_and_verify");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_example_with_lookup => "example_with_lookup");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_example_with_ffadd => "example_with_ffadd");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_example_with_xor => "example_with_xor");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_example_with_rot => "example_with_rot");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_example_with_foreign_field_mul => "example_with_foreign_field_mul");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_example_with_range_check => "example_with_range_check");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_example_with_range_check0 => "example_with_range_check0");
                 decl_func!(w, env, caml_pasta_fq_plonk_proof_verify => "verify");
                 decl_func!(w, env, caml_pasta_fq_plonk_proof_batch_verify => "batch_verify");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_verify_result => "verify_result");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_check_shape => "check_shape");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_check_shape_with_handle => "check_shape_with_handle");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_batch_verify_result => "batch_verify_result");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_batch_verify_bisect => "batch_verify_bisect");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_verify_with_handle => "verify_with_handle");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_batch_verify_with_handle => "batch_verify_with_handle");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_verify_result_with_handle => "verify_result_with_handle");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_batch_verify_result_with_handle => "batch_verify_result_with_handle");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_to_bytes => "to_bytes");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_of_bytes => "of_bytes");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_to_json => "to_json");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_of_json => "of_json");
                 decl_func!(w, env, caml_pasta_fq_plonk_proof_dummy => "dummy");
+                decl_func!(w, env, caml_pasta_fq_plonk_proof_dummy_with_shape => "dummy_with_shape");
                 decl_func!(w, env, caml_pasta_fq_plonk_proof_deep_copy => "deep_copy");
             });
+
+            decl_func!(w, env, caml_pasta_plonk_proof_batch_verify_mixed => "batch_verify_mixed");
         });
     });
 }
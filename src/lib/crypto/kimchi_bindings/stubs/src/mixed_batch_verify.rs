@@ -0,0 +1,162 @@
+//! Verifies step (Vesta/Fp) and wrap (Pallas/Fq) proofs, and their pending
+//! accumulators, in a single call.
+
+use crate::{
+    arkworks::{CamlFp, CamlFq, CamlGPallas, CamlGVesta},
+    pasta_fp_plonk_verifier_index::CamlPastaFpPlonkVerifierIndex,
+    pasta_fq_plonk_verifier_index::CamlPastaFqPlonkVerifierIndex,
+    srs::{fp::CamlFpSrs, fq::CamlFqSrs, LagrangeBases},
+    urs_utils::batch_dlog_accumulator_check,
+};
+use groupmap::GroupMap;
+use kimchi::{
+    proof::ProverProof,
+    prover::caml::CamlProofWithPublic,
+    verifier::{batch_verify, Context},
+    verifier_index::VerifierIndex,
+};
+use mina_curves::pasta::{Fp, Fq, Pallas, PallasParameters, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+use poly_commitment::{commitment::CommitmentCurve, evaluation_proof::OpeningProof};
+
+/// The proofs of one curve to verify, and the accumulators to check with `srs`.
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlBatchVerifyInput<Index, Proof, Srs, G, F> {
+    pub indexes: Vec<Index>,
+    pub proofs: Vec<Proof>,
+    pub srs: Srs,
+    pub accumulator_comms: Vec<G>,
+    pub accumulator_chals: Vec<F>,
+}
+
+type CamlFpBatchVerifyInput = CamlBatchVerifyInput<
+    CamlPastaFpPlonkVerifierIndex,
+    CamlProofWithPublic<CamlGVesta, CamlFp>,
+    CamlFpSrs,
+    CamlGVesta,
+    CamlFp,
+>;
+
+type CamlFqBatchVerifyInput = CamlBatchVerifyInput<
+    CamlPastaFqPlonkVerifierIndex,
+    CamlProofWithPublic<CamlGPallas, CamlFq>,
+    CamlFqSrs,
+    CamlGPallas,
+    CamlFq,
+>;
+
+/// Verifies both batches of proofs and checks both sets of accumulators, in parallel
+/// in the prover pool. Returns `true` if all four checks pass, and raises `Invalid_argument`
+/// if either input does not have as many indexes as proofs.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_plonk_proof_batch_verify_mixed(
+    fp: CamlFpBatchVerifyInput,
+    fq: CamlFqBatchVerifyInput,
+) -> Result<bool, ocaml::Error> {
+    if fp.indexes.len() != fp.proofs.len() || fq.indexes.len() != fq.proofs.len() {
+        return Err(
+            ocaml::Error::invalid_argument("caml_pasta_plonk_proof_batch_verify_mixed")
+                .err()
+                .unwrap(),
+        );
+    }
+
+    let fp_proofs: Vec<(
+        VerifierIndex<Vesta, OpeningProof<Vesta>>,
+        ProverProof<Vesta, OpeningProof<Vesta>>,
+        Vec<Fp>,
+    )> = fp
+        .indexes
+        .into_iter()
+        .zip(fp.proofs)
+        .map(|(index, proof)| {
+            let (proof, public_input) = proof.into();
+            (index.into(), proof, public_input)
+        })
+        .collect();
+    let fq_proofs: Vec<(
+        VerifierIndex<Pallas, OpeningProof<Pallas>>,
+        ProverProof<Pallas, OpeningProof<Pallas>>,
+        Vec<Fq>,
+    )> = fq
+        .indexes
+        .into_iter()
+        .zip(fq.proofs)
+        .map(|(index, proof)| {
+            let (proof, public_input) = proof.into();
+            (index.into(), proof, public_input)
+        })
+        .collect();
+
+    let fp_comms: Vec<Vesta> = fp.accumulator_comms.into_iter().map(Into::into).collect();
+    let fp_chals: Vec<Fp> = fp.accumulator_chals.into_iter().map(Into::into).collect();
+    let fq_comms: Vec<Pallas> = fq.accumulator_comms.into_iter().map(Into::into).collect();
+    let fq_chals: Vec<Fq> = fq.accumulator_chals.into_iter().map(Into::into).collect();
+    let (fp_srs, fq_srs) = (fp.srs, fq.srs);
+
+    let verify_fp = || {
+        let contexts: Vec<_> = fp_proofs
+            .iter()
+            .map(|(verifier_index, proof, public_input)| Context {
+                verifier_index,
+                proof,
+                public_input,
+            })
+            .collect();
+        let group_map = <Vesta as CommitmentCurve>::Map::setup();
+        batch_verify::<
+            Vesta,
+            DefaultFqSponge<VestaParameters, PlonkSpongeConstantsKimchi>,
+            DefaultFrSponge<Fp, PlonkSpongeConstantsKimchi>,
+            OpeningProof<Vesta>,
+        >(&group_map, &contexts)
+        .is_ok()
+    };
+    let verify_fq = || {
+        let contexts: Vec<_> = fq_proofs
+            .iter()
+            .map(|(verifier_index, proof, public_input)| Context {
+                verifier_index,
+                proof,
+                public_input,
+            })
+            .collect();
+        let group_map = <Pallas as CommitmentCurve>::Map::setup();
+        batch_verify::<
+            Pallas,
+            DefaultFqSponge<PallasParameters, PlonkSpongeConstantsKimchi>,
+            DefaultFrSponge<Fq, PlonkSpongeConstantsKimchi>,
+            OpeningProof<Pallas>,
+        >(&group_map, &contexts)
+        .is_ok()
+    };
+    let check_fp = || batch_dlog_accumulator_check(&fp_srs, &fp_comms, &fp_chals);
+    let check_fq = || batch_dlog_accumulator_check(&fq_srs, &fq_comms, &fq_chals);
+
+    crate::srs::releasing_runtime(|| {
+        // kimchi's verifier commits to the public inputs with the Lagrange bases
+        let fp_bases = fp_proofs
+            .iter()
+            .fold(LagrangeBases::default(), |bases, (verifier_index, _, _)| {
+                bases.with(verifier_index.srs(), verifier_index.domain)
+            });
+        let _bases = fq_proofs
+            .iter()
+            .fold(fp_bases, |bases, (verifier_index, _, _)| {
+                bases.with(verifier_index.srs(), verifier_index.domain)
+            })
+            .read();
+
+        let ((fp_ok, fq_ok), (fp_acc_ok, fq_acc_ok)) = crate::prover_pool::install(|| {
+            rayon::join(
+                || rayon::join(verify_fp, verify_fq),
+                || rayon::join(check_fp, check_fq),
+            )
+        });
+        Ok(fp_ok && fq_ok && fp_acc_ok && fq_acc_ok)
+    })
+}
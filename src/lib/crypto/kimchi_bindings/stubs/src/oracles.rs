@@ -1,21 +1,33 @@
-use crate::pasta_fp_plonk_verifier_index::CamlPastaFpPlonkVerifierIndex;
+use crate::pasta_fp_plonk_verifier_index::{
+    CamlPastaFpPlonkVerifierIndex, CamlPastaFpPlonkVerifierIndexHandle,
+};
+use crate::transcript::{
+    evaluations_in_absorption_order, fq_challenge_names, label_transcript,
+    CamlOraclesWithTranscript, CamlSponge, CamlTranscriptValue, SpongeOperation, TranscriptEvent,
+};
+use ark_ec::AffineCurve;
 use ark_ff::One;
 use kimchi::circuits::scalars::{caml::CamlRandomOracles, RandomOracles};
-use kimchi::proof::ProverProof;
+use kimchi::plonk_sponge::FrSponge;
+use kimchi::proof::{PointEvaluations, ProofEvaluations, ProverProof};
 use kimchi::{
+    error::VerifyError,
     prover::caml::{CamlProofWithPublic, CamlProverProof},
     verifier_index::VerifierIndex,
 };
 use mina_poseidon::{
     self,
     constants::PlonkSpongeConstantsKimchi,
-    sponge::{DefaultFqSponge, DefaultFrSponge},
+    poseidon::ArithmeticSpongeParams,
+    sponge::{DefaultFqSponge, DefaultFrSponge, ScalarChallenge},
     FqSponge,
 };
 use paste::paste;
 use poly_commitment::commitment::{caml::CamlPolyComm, shift_scalar, PolyComm};
 use poly_commitment::evaluation_proof::OpeningProof;
 use poly_commitment::SRS;
+use rayon::prelude::*;
+use std::cell::RefCell;
 
 #[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
 pub struct CamlOracles<F> {
@@ -26,53 +38,167 @@ pub struct CamlOracles<F> {
 }
 
 macro_rules! impl_oracles {
-    ($CamlF: ty, $F: ty, $CamlG: ty, $G: ty, $index: ty, $curve_params: ty) => {
-        paste! {
-            #[ocaml_gen::func]
-            #[ocaml::func]
-            pub fn [<$F:snake _oracles_create>](
-                lgr_comm: Vec<CamlPolyComm<$CamlG>>,
-                index: $index,
-                proof: CamlProofWithPublic<$CamlG, $CamlF>,
-            ) -> Result<CamlOracles<$CamlF>, ocaml::Error> {
-                let index: VerifierIndex<$G, OpeningProof<$G>> = index.into();
+    ($CamlF: ty, $F: ty, $CamlG: ty, $G: ty, $CamlBaseF: ty, $index: ty, $index_handle: ty, $curve_params: ty) => {
+        type KimchiFqSponge = DefaultFqSponge<$curve_params, PlonkSpongeConstantsKimchi>;
+        type KimchiFrSponge = DefaultFrSponge<$F, PlonkSpongeConstantsKimchi>;
 
-                let lgr_comm: Vec<PolyComm<$G>> = lgr_comm
-                    .into_iter()
-                    .take(proof.proof.public.len())
-                    .map(Into::into)
-                    .collect();
-                let lgr_comm_refs: Vec<_> = lgr_comm.iter().collect();
-
-                let p_comm = PolyComm::<$G>::multi_scalar_mul(
-                    &lgr_comm_refs,
-                    &proof
-                        .proof
-                        .public
-                        .iter()
-                        .map(Into::<$F>::into)
-                        .map(|s| -s)
-                        .collect::<Vec<_>>(),
-                );
+        thread_local! {
+            /// The values that went through the recording sponges of this thread.
+            static TRANSCRIPT: RefCell<Vec<TranscriptEvent<$F, $G, <$G as AffineCurve>::BaseField>>> =
+                RefCell::new(vec![]);
+        }
 
-                let p_comm = {
-                    index
-                        .srs()
-                        .mask_custom(
-                            p_comm.clone(),
-                            &p_comm.map(|_| $F::one()),
-                        )
-                        .unwrap()
-                        .commitment
-                };
+        fn record(
+            sponge: CamlSponge,
+            operation: SpongeOperation,
+            value: CamlTranscriptValue<$F, $G, <$G as AffineCurve>::BaseField>,
+        ) {
+            TRANSCRIPT.with(|transcript| {
+                transcript.borrow_mut().push(TranscriptEvent {
+                    sponge,
+                    operation,
+                    value,
+                })
+            })
+        }
 
-                let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<$F>) = proof.into();
+        /// The Fq-sponge of kimchi, recording everything it absorbs and squeezes.
+        #[derive(Clone)]
+        struct RecordingFqSponge(KimchiFqSponge);
+
+        impl FqSponge<<$G as AffineCurve>::BaseField, $G, $F> for RecordingFqSponge {
+            fn new(params: &'static ArithmeticSpongeParams<<$G as AffineCurve>::BaseField>) -> Self {
+                RecordingFqSponge(KimchiFqSponge::new(params))
+            }
+
+            fn absorb_g(&mut self, g: &[$G]) {
+                for g in g {
+                    record(CamlSponge::FqSponge, SpongeOperation::Absorb, CamlTranscriptValue::Point(*g));
+                }
+                self.0.absorb_g(g)
+            }
+
+            fn absorb_fq(&mut self, x: &[<$G as AffineCurve>::BaseField]) {
+                for x in x {
+                    record(CamlSponge::FqSponge, SpongeOperation::Absorb, CamlTranscriptValue::BaseField(*x));
+                }
+                self.0.absorb_fq(x)
+            }
+
+            fn absorb_fr(&mut self, x: &[$F]) {
+                for x in x {
+                    record(CamlSponge::FqSponge, SpongeOperation::Absorb, CamlTranscriptValue::Scalar(*x));
+                }
+                self.0.absorb_fr(x)
+            }
+
+            fn challenge(&mut self) -> $F {
+                let x = self.0.challenge();
+                record(CamlSponge::FqSponge, SpongeOperation::Challenge, CamlTranscriptValue::Scalar(x));
+                x
+            }
+
+            fn challenge_fq(&mut self) -> <$G as AffineCurve>::BaseField {
+                let x = self.0.challenge_fq();
+                record(CamlSponge::FqSponge, SpongeOperation::Challenge, CamlTranscriptValue::BaseField(x));
+                x
+            }
+
+            fn digest(self) -> $F {
+                let x = self.0.digest();
+                record(CamlSponge::FqSponge, SpongeOperation::Digest, CamlTranscriptValue::Scalar(x));
+                x
+            }
+
+            fn digest_fq(self) -> <$G as AffineCurve>::BaseField {
+                let x = self.0.digest_fq();
+                record(CamlSponge::FqSponge, SpongeOperation::IndexDigest, CamlTranscriptValue::BaseField(x));
+                x
+            }
+        }
+
+        /// The Fr-sponge of kimchi, recording everything it absorbs and squeezes.
+        struct RecordingFrSponge(KimchiFrSponge);
+
+        impl FrSponge<$F> for RecordingFrSponge {
+            fn new(params: &'static ArithmeticSpongeParams<$F>) -> Self {
+                RecordingFrSponge(KimchiFrSponge::new(params))
+            }
+
+            fn absorb(&mut self, x: &$F) {
+                record(CamlSponge::FrSponge, SpongeOperation::Absorb, CamlTranscriptValue::Scalar(*x));
+                self.0.absorb(x)
+            }
+
+            fn absorb_multiple(&mut self, x: &[$F]) {
+                for x in x {
+                    record(CamlSponge::FrSponge, SpongeOperation::Absorb, CamlTranscriptValue::Scalar(*x));
+                }
+                self.0.absorb_multiple(x)
+            }
+
+            fn challenge(&mut self) -> ScalarChallenge<$F> {
+                let x = self.0.challenge();
+                record(CamlSponge::FrSponge, SpongeOperation::Challenge, CamlTranscriptValue::Scalar(x.0));
+                x
+            }
+
+            fn digest(self) -> $F {
+                let x = self.0.digest();
+                record(CamlSponge::FrSponge, SpongeOperation::Digest, CamlTranscriptValue::Scalar(x));
+                x
+            }
+
+            // absorbs the evaluations one polynomial at a time, as kimchi's sponge does,
+            // so that they are recorded as they go in
+            fn absorb_evaluations(&mut self, e: &ProofEvaluations<PointEvaluations<Vec<$F>>>) {
+                for point in evaluations_in_absorption_order(e) {
+                    self.absorb_multiple(&point.zeta);
+                    self.absorb_multiple(&point.zeta_omega);
+                }
+            }
+        }
+
+        paste! {
+            /// Computes the oracles of `proof` with the given sponges, where `lgr_comm`
+            /// are the commitments to the Lagrange basis of the domain of `index`.
+            ///
+            /// `public_comm` is the commitment to the public input polynomial, as kimchi's
+            /// verifier absorbs it (negated, and masked with the blinding of 1). If the caller
+            /// already has it, `lgr_comm` is not used and the MSM is skipped.
+            fn [<$F:snake _proof_oracles>]<EFqSponge, EFrSponge>(
+                lgr_comm: &[PolyComm<$G>],
+                index: &VerifierIndex<$G, OpeningProof<$G>>,
+                proof: &ProverProof<$G, OpeningProof<$G>>,
+                public_input: &[$F],
+                public_comm: Option<&PolyComm<$G>>,
+            ) -> Result<CamlOracles<$CamlF>, VerifyError>
+            where
+                EFqSponge: Clone + FqSponge<<$G as AffineCurve>::BaseField, $G, $F>,
+                EFrSponge: FrSponge<$F>,
+            {
+                let p_comm = match public_comm {
+                    Some(p_comm) => p_comm.clone(),
+                    None => {
+                        let lgr_comm_refs: Vec<_> =
+                            lgr_comm.iter().take(public_input.len()).collect();
+                        let p_comm = PolyComm::<$G>::multi_scalar_mul(
+                            &lgr_comm_refs,
+                            &public_input.iter().map(|s| -*s).collect::<Vec<_>>(),
+                        );
+                        index
+                            .srs()
+                            .mask_custom(
+                                p_comm.clone(),
+                                &p_comm.map(|_| $F::one()),
+                            )
+                            .unwrap()
+                            .commitment
+                    }
+                };
 
                 let oracles_result =
-                    proof.oracles::<
-                        DefaultFqSponge<$curve_params, PlonkSpongeConstantsKimchi>,
-                        DefaultFrSponge<$F, PlonkSpongeConstantsKimchi>,
-                    >(&index, &p_comm, Some(&public_input))?;
+                    proof.oracles::<EFqSponge, EFrSponge>(index, &p_comm, Some(public_input))?;
 
                 let (mut sponge, combined_inner_product, p_eval, digest, oracles) = (
                     oracles_result.fq_sponge,
@@ -99,6 +225,52 @@ macro_rules! impl_oracles {
                 })
             }
 
+            /// Same as the function above, for the OCaml representations of the arguments.
+            /// This is the single path behind all the `_oracles_create*` functions.
+            fn [<$F:snake _oracles>]<EFqSponge, EFrSponge>(
+                lgr_comm: Vec<CamlPolyComm<$CamlG>>,
+                index: &VerifierIndex<$G, OpeningProof<$G>>,
+                proof: CamlProofWithPublic<$CamlG, $CamlF>,
+                public_comm: Option<CamlPolyComm<$CamlG>>,
+            ) -> Result<CamlOracles<$CamlF>, ocaml::Error>
+            where
+                EFqSponge: Clone + FqSponge<<$G as AffineCurve>::BaseField, $G, $F>,
+                EFrSponge: FrSponge<$F>,
+            {
+                let public_comm: Option<PolyComm<$G>> = public_comm.map(Into::into);
+                let lgr_comm: Vec<PolyComm<$G>> = match public_comm {
+                    Some(_) => vec![],
+                    None => lgr_comm
+                        .into_iter()
+                        .take(proof.proof.public.len())
+                        .map(Into::into)
+                        .collect(),
+                };
+                let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<$F>) = proof.into();
+
+                let oracles = crate::srs::releasing_runtime(|| {
+                    [<$F:snake _proof_oracles>]::<EFqSponge, EFrSponge>(
+                        &lgr_comm,
+                        index,
+                        &proof,
+                        &public_input,
+                        public_comm.as_ref(),
+                    )
+                })?;
+                Ok(oracles)
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<$F:snake _oracles_create>](
+                lgr_comm: Vec<CamlPolyComm<$CamlG>>,
+                index: $index,
+                proof: CamlProofWithPublic<$CamlG, $CamlF>,
+            ) -> Result<CamlOracles<$CamlF>, ocaml::Error> {
+                let index: VerifierIndex<$G, OpeningProof<$G>> = index.into();
+                [<$F:snake _oracles>]::<KimchiFqSponge, KimchiFrSponge>(lgr_comm, &index, proof, None)
+            }
+
             #[ocaml_gen::func]
             #[ocaml::func]
             pub fn [<$F:snake _oracles_create_no_public>](
@@ -112,66 +284,122 @@ macro_rules! impl_oracles {
                 };
 
                 let index: VerifierIndex<$G, OpeningProof<$G>> = index.into();
+                [<$F:snake _oracles>]::<KimchiFqSponge, KimchiFrSponge>(lgr_comm, &index, proof, None)
+            }
+
+            /// Same as `create_no_public`, with a verifier index that was converted beforehand.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<$F:snake _oracles_create_with_handle>](
+                lgr_comm: Vec<CamlPolyComm<$CamlG>>,
+                index: $index_handle,
+                proof: CamlProverProof<$CamlG, $CamlF>,
+            ) -> Result<CamlOracles<$CamlF>, ocaml::Error> {
+                let proof = CamlProofWithPublic {
+                    proof,
+                    public_evals: None,
+                };
+
+                [<$F:snake _oracles>]::<KimchiFqSponge, KimchiFrSponge>(lgr_comm, &index, proof, None)
+            }
+
+            /// Same as `create`, with the public evaluations and the commitment to the public input
+            /// (as absorbed by kimchi, see above) when the caller already has them.
+            /// `lgr_comm` is only used when `public_comm` is `None`.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<$F:snake _oracles_create_with_public>](
+                lgr_comm: Vec<CamlPolyComm<$CamlG>>,
+                index: $index,
+                proof: CamlProverProof<$CamlG, $CamlF>,
+                public_evals: Option<PointEvaluations<Vec<$CamlF>>>,
+                public_comm: Option<CamlPolyComm<$CamlG>>,
+            ) -> Result<CamlOracles<$CamlF>, ocaml::Error> {
+                let proof = CamlProofWithPublic {
+                    proof,
+                    public_evals,
+                };
+
+                let index: VerifierIndex<$G, OpeningProof<$G>> = index.into();
+                [<$F:snake _oracles>]::<KimchiFqSponge, KimchiFrSponge>(lgr_comm, &index, proof, public_comm)
+            }
 
+            /// Same as `create` for many proofs of the same circuit. The verifier index and
+            /// `lgr_comm` are converted once, and the oracles are computed in parallel in
+            /// the prover pool.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<$F:snake _oracles_create_batch>](
+                lgr_comm: Vec<CamlPolyComm<$CamlG>>,
+                index: $index,
+                proofs: Vec<CamlProverProof<$CamlG, $CamlF>>,
+            ) -> Result<Vec<CamlOracles<$CamlF>>, ocaml::Error> {
+                let index: VerifierIndex<$G, OpeningProof<$G>> = index.into();
+                let max_public = proofs.iter().map(|proof| proof.public.len()).max().unwrap_or(0);
                 let lgr_comm: Vec<PolyComm<$G>> = lgr_comm
                     .into_iter()
-                    .take(proof.proof.public.len())
+                    .take(max_public)
                     .map(Into::into)
                     .collect();
-                let lgr_comm_refs: Vec<_> = lgr_comm.iter().collect();
-
-                let p_comm = PolyComm::<$G>::multi_scalar_mul(
-                    &lgr_comm_refs,
-                    &proof
-                        .proof
-                        .public
-                        .iter()
-                        .map(Into::<$F>::into)
-                        .map(|s| -s)
-                        .collect::<Vec<_>>(),
-                );
+                let proofs: Vec<(ProverProof<$G, OpeningProof<$G>>, Vec<$F>)> = proofs
+                    .into_iter()
+                    .map(|proof| {
+                        CamlProofWithPublic {
+                            proof,
+                            public_evals: None,
+                        }
+                        .into()
+                    })
+                    .collect();
 
-                let p_comm = {
-                    index
-                        .srs()
-                        .mask_custom(
-                            p_comm.clone(),
-                            &p_comm.map(|_| $F::one()),
-                        )
-                        .unwrap()
-                        .commitment
-                };
+                let oracles = crate::srs::releasing_runtime(|| {
+                    crate::prover_pool::install(|| {
+                        proofs
+                            .par_iter()
+                            .map(|(proof, public_input)| {
+                                [<$F:snake _proof_oracles>]::<KimchiFqSponge, KimchiFrSponge>(
+                                    &lgr_comm,
+                                    &index,
+                                    proof,
+                                    public_input,
+                                    None,
+                                )
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                })?;
+                Ok(oracles)
+            }
 
-                let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<$F>) = proof.into();
+            /// Same as `create`, but also returns every value absorbed and squeezed by the sponges,
+            /// in order, to find where two verifiers diverge.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<$F:snake _oracles_create_debug>](
+                lgr_comm: Vec<CamlPolyComm<$CamlG>>,
+                index: $index,
+                proof: CamlProverProof<$CamlG, $CamlF>,
+            ) -> Result<CamlOraclesWithTranscript<$CamlF, $CamlG, $CamlBaseF>, ocaml::Error> {
+                let proof = CamlProofWithPublic {
+                    proof,
+                    public_evals: None,
+                };
 
-                let oracles_result =
-                    proof.oracles::<
-                        DefaultFqSponge<$curve_params, PlonkSpongeConstantsKimchi>,
-                        DefaultFrSponge<$F, PlonkSpongeConstantsKimchi>,
-                    >(&index, &p_comm, Some(&public_input))?;
+                let index: VerifierIndex<$G, OpeningProof<$G>> = index.into();
+                let joint_lookup_used = index
+                    .lookup_index
+                    .as_ref()
+                    .map_or(false, |lookup| lookup.joint_lookup_used);
 
-                let (mut sponge, combined_inner_product, p_eval, digest, oracles) = (
-                    oracles_result.fq_sponge,
-                    oracles_result.combined_inner_product,
-                    oracles_result.public_evals,
-                    oracles_result.digest,
-                    oracles_result.oracles,
+                TRANSCRIPT.with(|transcript| transcript.borrow_mut().clear());
+                let oracles = [<$F:snake _oracles>]::<RecordingFqSponge, RecordingFrSponge>(
+                    lgr_comm, &index, proof, None,
                 );
+                let events = TRANSCRIPT.with(|transcript| std::mem::take(&mut *transcript.borrow_mut()));
 
-                sponge.absorb_fr(&[shift_scalar::<$G>(combined_inner_product)]);
-
-                let opening_prechallenges = proof
-                    .proof
-                    .prechallenges(&mut sponge)
-                    .into_iter()
-                    .map(|x| x.0.into())
-                    .collect();
-
-                Ok(CamlOracles {
-                    o: oracles.into(),
-                    p_eval: (p_eval[0][0].into(), p_eval[1][0].into()),
-                    opening_prechallenges,
-                    digest_before_evaluations: digest.into(),
+                Ok(CamlOraclesWithTranscript {
+                    oracles: oracles?,
+                    transcript: label_transcript(events, &fq_challenge_names(joint_lookup_used)),
                 })
             }
 
@@ -189,12 +417,121 @@ macro_rules! impl_oracles {
                 x
             }
         }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+            use kimchi::curve::KimchiCurve;
+            use std::cell::Cell;
+
+            /// Evaluations of every polynomial, all distinct, so that the order matters.
+            fn distinct_evaluations() -> ProofEvaluations<PointEvaluations<Vec<$F>>> {
+                let counter = Cell::new(0u64);
+                let next = || {
+                    counter.set(counter.get() + 1);
+                    <$F>::from(counter.get())
+                };
+                let eval = || PointEvaluations {
+                    zeta: vec![next(), next()],
+                    zeta_omega: vec![next(), next()],
+                };
+                ProofEvaluations {
+                    public: Some(eval()),
+                    w: std::array::from_fn(|_| eval()),
+                    coefficients: std::array::from_fn(|_| eval()),
+                    z: eval(),
+                    s: std::array::from_fn(|_| eval()),
+                    generic_selector: eval(),
+                    poseidon_selector: eval(),
+                    complete_add_selector: eval(),
+                    mul_selector: eval(),
+                    emul_selector: eval(),
+                    endomul_scalar_selector: eval(),
+                    range_check0_selector: Some(eval()),
+                    range_check1_selector: Some(eval()),
+                    foreign_field_add_selector: Some(eval()),
+                    foreign_field_mul_selector: Some(eval()),
+                    xor_selector: Some(eval()),
+                    rot_selector: Some(eval()),
+                    lookup_aggregation: Some(eval()),
+                    lookup_table: Some(eval()),
+                    lookup_sorted: std::array::from_fn(|i| (i % 2 == 0).then(eval)),
+                    runtime_lookup_table: Some(eval()),
+                    runtime_lookup_table_selector: Some(eval()),
+                    xor_lookup_selector: Some(eval()),
+                    lookup_gate_lookup_selector: Some(eval()),
+                    range_check_lookup_selector: Some(eval()),
+                    foreign_field_mul_lookup_selector: None,
+                }
+            }
+
+            #[test]
+            fn recorded_absorbs_replay_to_the_same_challenges() {
+                let evals = distinct_evaluations();
+                let fq_params = <$G as KimchiCurve>::other_curve_sponge_params();
+                let fr_params = <$G as KimchiCurve>::sponge_params();
+                let g = <$G as AffineCurve>::prime_subgroup_generator();
+                TRANSCRIPT.with(|transcript| transcript.borrow_mut().clear());
+
+                let mut fq_sponge = RecordingFqSponge::new(fq_params);
+                fq_sponge.absorb_g(&[g]);
+                fq_sponge.absorb_fr(&[<$F>::from(3u64)]);
+                let beta = fq_sponge.challenge();
+                let digest = fq_sponge.digest();
+
+                let mut fr_sponge = RecordingFrSponge::new(fr_params);
+                fr_sponge.absorb(&digest);
+                fr_sponge.absorb_evaluations(&evals);
+                let v = fr_sponge.challenge().0;
+                fr_sponge.absorb_multiple(&[<$F>::from(5u64), <$F>::from(7u64)]);
+                let u = fr_sponge.challenge().0;
+
+                // the recording sponges squeeze what kimchi's own sponges do
+                let mut kimchi_fr_sponge = KimchiFrSponge::new(fr_params);
+                kimchi_fr_sponge.absorb(&digest);
+                kimchi_fr_sponge.absorb_evaluations(&evals);
+                assert_eq!(kimchi_fr_sponge.challenge().0, v);
+
+                // and absorbing the recorded values one by one squeezes the same challenges
+                let events = TRANSCRIPT.with(|transcript| std::mem::take(&mut *transcript.borrow_mut()));
+                let mut fq_replay = KimchiFqSponge::new(fq_params);
+                let mut fr_replay = KimchiFrSponge::new(fr_params);
+                let mut squeezed = vec![];
+                for event in events {
+                    match (event.sponge, event.operation, event.value) {
+                        (CamlSponge::FqSponge, SpongeOperation::Absorb, CamlTranscriptValue::Point(g)) => {
+                            fq_replay.absorb_g(&[g])
+                        }
+                        (CamlSponge::FqSponge, SpongeOperation::Absorb, CamlTranscriptValue::Scalar(x)) => {
+                            fq_replay.absorb_fr(&[x])
+                        }
+                        (CamlSponge::FqSponge, SpongeOperation::Challenge, CamlTranscriptValue::Scalar(x)) => {
+                            assert_eq!(fq_replay.challenge(), x);
+                            squeezed.push(x)
+                        }
+                        (CamlSponge::FqSponge, SpongeOperation::Digest, CamlTranscriptValue::Scalar(x)) => {
+                            assert_eq!(fq_replay.clone().digest(), x);
+                            squeezed.push(x)
+                        }
+                        (CamlSponge::FrSponge, SpongeOperation::Absorb, CamlTranscriptValue::Scalar(x)) => {
+                            fr_replay.absorb(&x)
+                        }
+                        (CamlSponge::FrSponge, SpongeOperation::Challenge, CamlTranscriptValue::Scalar(x)) => {
+                            assert_eq!(fr_replay.challenge().0, x);
+                            squeezed.push(x)
+                        }
+                        _ => panic!("unexpected transcript event"),
+                    }
+                }
+                assert_eq!(squeezed, vec![beta, digest, v, u]);
+            }
+        }
     }
 }
 
 pub mod fp {
     use super::*;
-    use crate::arkworks::{CamlFp, CamlGVesta};
+    use crate::arkworks::{CamlFp, CamlFq, CamlGVesta};
     use mina_curves::pasta::{Fp, Vesta, VestaParameters};
 
     impl_oracles!(
@@ -202,7 +539,9 @@ pub mod fp {
         Fp,
         CamlGVesta,
         Vesta,
+        CamlFq,
         CamlPastaFpPlonkVerifierIndex,
+        CamlPastaFpPlonkVerifierIndexHandle,
         VestaParameters
     );
 }
@@ -210,9 +549,11 @@ pub mod fp {
 pub mod fq {
     use super::*;
     use crate::{
-        arkworks::{CamlFq, CamlGPallas},
+        arkworks::{CamlFp, CamlFq, CamlGPallas},
         oracles::CamlOracles,
-        pasta_fq_plonk_verifier_index::CamlPastaFqPlonkVerifierIndex,
+        pasta_fq_plonk_verifier_index::{
+            CamlPastaFqPlonkVerifierIndex, CamlPastaFqPlonkVerifierIndexHandle,
+        },
     };
     use mina_curves::pasta::{Fq, Pallas, PallasParameters};
 
@@ -221,7 +562,9 @@ pub mod fq {
         Fq,
         CamlGPallas,
         Pallas,
+        CamlFp,
         CamlPastaFqPlonkVerifierIndex,
+        CamlPastaFqPlonkVerifierIndexHandle,
         PallasParameters
     );
 }
@@ -1,5 +1,10 @@
 use crate::arkworks::CamlFp;
-use crate::{gate_vector::fp::CamlPastaFpPlonkGateVectorPtr, srs::fp::CamlFpSrs};
+use crate::{
+    caml::caml_result::CamlResult,
+    gate_vector::{fp::CamlPastaFpPlonkGateVectorPtr, SerializedCircuit},
+    setup_error::{check_setup, CamlSetupError},
+    srs::fp::CamlFpSrs,
+};
 use ark_poly::EvaluationDomain;
 use kimchi::circuits::lookup::runtime_tables::caml::CamlRuntimeTableCfg;
 use kimchi::circuits::lookup::runtime_tables::RuntimeTableCfg;
@@ -40,6 +45,101 @@ impl ocaml::custom::Custom for CamlPastaFpPlonkIndex {
     };
 }
 
+/// Builds the constraint system for `gates`.
+pub fn create_constraint_system(
+    gates: Vec<CircuitGate<Fp>>,
+    public: usize,
+    lookup_tables: Vec<LookupTable<Fp>>,
+    runtime_tables: Vec<RuntimeTableCfg<Fp>>,
+    prev_challenges: usize,
+    srs: &CamlFpSrs,
+) -> Result<ConstraintSystem<Fp>, CamlSetupError> {
+    // the polynomials of step circuits are split in chunks of the size of the SRS,
+    // so their domain is not bounded by it
+    check_setup(&gates, public, &lookup_tables, &runtime_tables)?;
+
+    ConstraintSystem::<Fp>::create(gates)
+        .public(public)
+        .prev_challenges(prev_challenges)
+        .max_poly_size(Some(srs.0.max_poly_size()))
+        .lookup(lookup_tables)
+        .runtime(if runtime_tables.is_empty() {
+            None
+        } else {
+            Some(runtime_tables)
+        })
+        .build()
+        .map_err(Into::into)
+}
+
+/// Builds the prover index for `cs`, and caches its verifier index digest.
+/// The SRS must already contain the Lagrange basis for `cs`, and keep it readable
+/// (see [crate::srs::lagrange_basis]).
+pub fn create_prover_index(
+    cs: ConstraintSystem<Fp>,
+    srs: &CamlFpSrs,
+) -> ProverIndex<Vesta, OpeningProof<Vesta>> {
+    // endo
+    let (endo_q, _endo_r) = poly_commitment::srs::endos::<Pallas>();
+
+    // create index
+    let mut index = ProverIndex::<Vesta, OpeningProof<Vesta>>::create(cs, endo_q, srs.0.clone());
+    // Compute and cache the verifier index digest
+    index.compute_verifier_index_digest::<DefaultFqSponge<VestaParameters, PlonkSpongeConstantsKimchi>>();
+
+    index
+}
+
+/// Builds the constraint system and the prover index for `gates`.
+/// Adding the Lagrange basis to the SRS may wait for the threads that read it.
+pub fn create_index(
+    gates: Vec<CircuitGate<Fp>>,
+    public: usize,
+    lookup_tables: Vec<LookupTable<Fp>>,
+    runtime_tables: Vec<RuntimeTableCfg<Fp>>,
+    prev_challenges: usize,
+    srs: &CamlFpSrs,
+) -> Result<ProverIndex<Vesta, OpeningProof<Vesta>>, CamlSetupError> {
+    let cs = create_constraint_system(
+        gates,
+        public,
+        lookup_tables,
+        runtime_tables,
+        prev_challenges,
+        srs,
+    )?;
+    let _bases = crate::srs::lagrange_basis(&srs.0, cs.domain.d1);
+    Ok(create_prover_index(cs, srs))
+}
+
+/// Same as [create_index], but releases the OCaml runtime lock during the heavy work.
+fn create_index_releasing_runtime(
+    gates: Vec<CircuitGate<Fp>>,
+    public: usize,
+    lookup_tables: Vec<LookupTable<Fp>>,
+    runtime_tables: Vec<RuntimeTableCfg<Fp>>,
+    prev_challenges: usize,
+    srs: &CamlFpSrs,
+) -> Result<CamlPastaFpPlonkIndex, CamlSetupError> {
+    let index = crate::srs::releasing_runtime(|| -> Result<_, CamlSetupError> {
+        let cs = crate::prover_pool::install(|| {
+            create_constraint_system(
+                gates,
+                public,
+                lookup_tables,
+                runtime_tables,
+                prev_challenges,
+                srs,
+            )
+        })?;
+        // added outside the prover pool, since it may wait for the proofs in the pool
+        let _bases = crate::srs::lagrange_basis(&srs.0, cs.domain.d1);
+        Ok(crate::prover_pool::install(|| create_prover_index(cs, srs)))
+    })?;
+
+    Ok(CamlPastaFpPlonkIndex(Box::new(index)))
+}
+
 #[ocaml_gen::func]
 #[ocaml::func]
 pub fn caml_pasta_fp_plonk_index_create(
@@ -66,41 +166,76 @@ pub fn caml_pasta_fp_plonk_index_create(
 
     let lookup_tables: Vec<LookupTable<Fp>> = lookup_tables.into_iter().map(Into::into).collect();
 
-    // create constraint system
-    let cs = match ConstraintSystem::<Fp>::create(gates)
-        .public(public as usize)
-        .prev_challenges(prev_challenges as usize)
-        .max_poly_size(Some(srs.0.max_poly_size()))
-        .lookup(lookup_tables)
-        .runtime(if runtime_tables.is_empty() {
-            None
-        } else {
-            Some(runtime_tables)
-        })
-        .build()
-    {
-        Err(e) => {
-            return Err(e.into())
-        }
-        Ok(cs) => cs,
-    };
+    create_index_releasing_runtime(
+        gates,
+        public as usize,
+        lookup_tables,
+        runtime_tables,
+        prev_challenges as usize,
+        &srs,
+    )
+    .map_err(Into::into)
+}
 
-    // endo
-    let (endo_q, _endo_r) = poly_commitment::srs::endos::<Pallas>();
+/// Same as `caml_pasta_fp_plonk_index_create`, but returns
+/// a [CamlSetupError] instead of raising an exception.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_index_try_create(
+    gates: CamlPastaFpPlonkGateVectorPtr,
+    public: ocaml::Int,
+    lookup_tables: Vec<CamlLookupTable<CamlFp>>,
+    runtime_tables: Vec<CamlRuntimeTableCfg<CamlFp>>,
+    prev_challenges: ocaml::Int,
+    srs: CamlFpSrs,
+) -> CamlResult<CamlPastaFpPlonkIndex, CamlSetupError> {
+    let gates: Vec<_> = gates.as_ref().0.clone();
 
-    // Unsafe if we are in a multi-core ocaml
-    {
-        let ptr: &mut poly_commitment::srs::SRS<Vesta> =
-            unsafe { &mut *(std::sync::Arc::as_ptr(&srs.0) as *mut _) };
-        ptr.add_lagrange_basis(cs.domain.d1);
-    }
+    let runtime_tables: Vec<RuntimeTableCfg<Fp>> =
+        runtime_tables.into_iter().map(Into::into).collect();
 
-    // create index
-    let mut index = ProverIndex::<Vesta, OpeningProof<Vesta>>::create(cs, endo_q, srs.clone());
-    // Compute and cache the verifier index digest
-    index.compute_verifier_index_digest::<DefaultFqSponge<VestaParameters, PlonkSpongeConstantsKimchi>>();
+    let lookup_tables: Vec<LookupTable<Fp>> = lookup_tables.into_iter().map(Into::into).collect();
 
-    Ok(CamlPastaFpPlonkIndex(Box::new(index)))
+    create_index_releasing_runtime(
+        gates,
+        public as usize,
+        lookup_tables,
+        runtime_tables,
+        prev_challenges as usize,
+        &srs,
+    )
+    .into()
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_index_create_from_circuit_json(
+    json: String,
+    lookup_tables: Vec<CamlLookupTable<CamlFp>>,
+    runtime_tables: Vec<CamlRuntimeTableCfg<CamlFp>>,
+    prev_challenges: ocaml::Int,
+    srs: CamlFpSrs,
+) -> Result<CamlPastaFpPlonkIndex, ocaml::Error> {
+    let circuit = SerializedCircuit::<Fp>::from_json(&json).map_err(|_| {
+        ocaml::Error::invalid_argument("caml_pasta_fp_plonk_index_create_from_circuit_json")
+            .err()
+            .unwrap()
+    })?;
+
+    let runtime_tables: Vec<RuntimeTableCfg<Fp>> =
+        runtime_tables.into_iter().map(Into::into).collect();
+
+    let lookup_tables: Vec<LookupTable<Fp>> = lookup_tables.into_iter().map(Into::into).collect();
+
+    create_index_releasing_runtime(
+        circuit.gates,
+        circuit.public_input_size,
+        lookup_tables,
+        runtime_tables,
+        prev_challenges as usize,
+        &srs,
+    )
+    .map_err(Into::into)
 }
 
 #[ocaml_gen::func]
@@ -1,9 +1,13 @@
-use crate::arkworks::{CamlFp, CamlGVesta};
+use crate::arkworks::{CamlFp, CamlFq, CamlGVesta};
+use crate::caml::caml_bytes_string::{CamlBytes, CamlBytesString};
+use crate::dummy::{CamlDummyShape, DummyShape};
 use crate::pasta_fp_plonk_index::CamlPastaFpPlonkIndexPtr;
 use crate::plonk_verifier_index::{
-    CamlPlonkDomain, CamlPlonkVerificationEvals, CamlPlonkVerifierIndex,
+    verifier_index_of_bytes, verifier_index_of_json, verifier_index_to_bytes,
+    verifier_index_to_json, CamlPlonkDomain, CamlPlonkVerificationEvals, CamlPlonkVerifierIndex,
 };
 use crate::srs::fp::CamlFpSrs;
+use crate::verifier_index_diff::{diff_verifier_indexes, CamlVerifierIndexChange};
 use ark_ec::AffineCurve;
 use ark_ff::One;
 use ark_poly::{EvaluationDomain, Radix2EvaluationDomain as Domain};
@@ -13,10 +17,12 @@ use kimchi::circuits::polynomials::permutation::Shifts;
 use kimchi::circuits::polynomials::permutation::{permutation_vanishing_polynomial, zk_w};
 use kimchi::circuits::wires::{COLUMNS, PERMUTS};
 use kimchi::{linearization::expr_linearization, verifier_index::VerifierIndex};
-use mina_curves::pasta::{Fp, Pallas, Vesta};
+use mina_curves::pasta::{Fp, Pallas, Vesta, VestaParameters};
+use mina_poseidon::{constants::PlonkSpongeConstantsKimchi, sponge::DefaultFqSponge};
 use poly_commitment::commitment::caml::CamlPolyComm;
 use poly_commitment::evaluation_proof::OpeningProof;
 use poly_commitment::{commitment::PolyComm, srs::SRS};
+use sha2::{Digest, Sha256};
 use std::convert::TryInto;
 use std::path::Path;
 use std::sync::Arc;
@@ -216,20 +222,129 @@ pub fn caml_pasta_fp_plonk_verifier_index_write(
     })
 }
 
+/// Encodes the verifier index without its SRS, see [verifier_index_to_bytes].
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_to_bytes(
+    index: CamlPastaFpPlonkVerifierIndex,
+) -> Result<CamlBytes, ocaml::Error> {
+    let index: VerifierIndex<Vesta, OpeningProof<Vesta>> = index.into();
+    verifier_index_to_bytes(&index, "fp")
+        .map(CamlBytes)
+        .map_err(ocaml::Error::Message)
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_of_bytes(
+    srs: CamlFpSrs,
+    bytes: CamlBytesString,
+) -> Result<CamlPastaFpPlonkVerifierIndex, ocaml::Error> {
+    verifier_index_of_bytes(bytes.0, "fp", srs.0)
+        .map(Into::into)
+        .map_err(ocaml::Error::Message)
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_to_json(
+    index: CamlPastaFpPlonkVerifierIndex,
+) -> Result<String, ocaml::Error> {
+    let index: VerifierIndex<Vesta, OpeningProof<Vesta>> = index.into();
+    verifier_index_to_json(index).map_err(ocaml::Error::Message)
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_of_json(
+    srs: CamlFpSrs,
+    json: String,
+) -> Result<CamlPastaFpPlonkVerifierIndex, ocaml::Error> {
+    verifier_index_of_json(&json, srs.0)
+        .map(Into::into)
+        .map_err(ocaml::Error::Message)
+}
+
+/// The Poseidon digest of the verifier index, the same as the one that
+/// the prover index caches in `compute_verifier_index_digest`.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_digest(index: CamlPastaFpPlonkVerifierIndex) -> CamlFq {
+    let index: VerifierIndex<Vesta, OpeningProof<Vesta>> = index.into();
+    index
+        .digest::<DefaultFqSponge<VestaParameters, PlonkSpongeConstantsKimchi>>()
+        .into()
+}
+
+/// The SHA-256 hash of the bytes returned by `to_bytes`, with nothing prepended.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_bytes_digest(
+    index: CamlPastaFpPlonkVerifierIndex,
+) -> Result<[u8; 32], ocaml::Error> {
+    let index: VerifierIndex<Vesta, OpeningProof<Vesta>> = index.into();
+    let bytes = verifier_index_to_bytes(&index, "fp").map_err(ocaml::Error::Message)?;
+    Ok(Sha256::digest(bytes).into())
+}
+
+/// The fields of `after` that differ from those of `before`, see [diff_verifier_indexes].
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_diff(
+    before: CamlPastaFpPlonkVerifierIndex,
+    after: CamlPastaFpPlonkVerifierIndex,
+) -> Vec<CamlVerifierIndexChange> {
+    let before: VerifierIndex<Vesta, OpeningProof<Vesta>> = before.into();
+    let after: VerifierIndex<Vesta, OpeningProof<Vesta>> = after.into();
+    diff_verifier_indexes(&before, &after)
+}
+
 #[ocaml_gen::func]
 #[ocaml::func]
 pub fn caml_pasta_fp_plonk_verifier_index_create(
     index: CamlPastaFpPlonkIndexPtr,
 ) -> CamlPastaFpPlonkVerifierIndex {
-    {
-        let ptr: &mut poly_commitment::srs::SRS<Vesta> =
-            unsafe { &mut *(std::sync::Arc::as_ptr(&index.as_ref().0.srs) as *mut _) };
-        ptr.add_lagrange_basis(index.as_ref().0.cs.domain.d1);
-    }
-    let verifier_index = index.as_ref().0.verifier_index();
+    let index = &index.as_ref().0;
+    let verifier_index = crate::srs::releasing_runtime(|| {
+        let _bases = crate::srs::lagrange_basis(&index.srs, index.cs.domain.d1);
+        index.verifier_index()
+    });
     verifier_index.into()
 }
 
+impl_shared_reference!(CamlPastaFpPlonkVerifierIndexHandle => VerifierIndex<Vesta, OpeningProof<Vesta>>);
+
+/// Creates a verifier index that stays on the Rust heap, so that it is not
+/// converted again by every function that uses it.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_create_handle(
+    index: CamlPastaFpPlonkIndexPtr,
+) -> CamlPastaFpPlonkVerifierIndexHandle {
+    let index = &index.as_ref().0;
+    let verifier_index = crate::srs::releasing_runtime(|| {
+        let _bases = crate::srs::lagrange_basis(&index.srs, index.cs.domain.d1);
+        index.verifier_index()
+    });
+    CamlPastaFpPlonkVerifierIndexHandle::new(verifier_index)
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_handle_of_index(
+    index: CamlPastaFpPlonkVerifierIndex,
+) -> CamlPastaFpPlonkVerifierIndexHandle {
+    CamlPastaFpPlonkVerifierIndexHandle::new(index.into())
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_handle_to_index(
+    handle: CamlPastaFpPlonkVerifierIndexHandle,
+) -> CamlPastaFpPlonkVerifierIndex {
+    (**handle).clone().into()
+}
+
 #[ocaml_gen::func]
 #[ocaml::func]
 pub fn caml_pasta_fp_plonk_verifier_index_shifts(log2_size: ocaml::Int) -> Vec<CamlFp> {
@@ -283,6 +398,18 @@ pub fn caml_pasta_fp_plonk_verifier_index_dummy() -> CamlPastaFpPlonkVerifierInd
     }
 }
 
+/// Same as `dummy`, with the commitments of a circuit of the given shape
+/// (see [DummyShape::verifier_index]).
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fp_plonk_verifier_index_dummy_with_shape(
+    shape: CamlDummyShape,
+    srs: CamlFpSrs,
+) -> Result<CamlPastaFpPlonkVerifierIndex, ocaml::Error> {
+    let shape: DummyShape = shape.try_into()?;
+    Ok(shape.verifier_index::<Vesta, _, _, _>(srs))
+}
+
 #[ocaml_gen::func]
 #[ocaml::func]
 pub fn caml_pasta_fp_plonk_verifier_index_deep_copy(
@@ -1,5 +1,10 @@
 use crate::arkworks::CamlFq;
-use crate::{gate_vector::fq::CamlPastaFqPlonkGateVectorPtr, srs::fq::CamlFqSrs};
+use crate::{
+    caml::caml_result::CamlResult,
+    gate_vector::{fq::CamlPastaFqPlonkGateVectorPtr, SerializedCircuit},
+    setup_error::{check_domain, check_setup, CamlSetupError},
+    srs::fq::CamlFqSrs,
+};
 use ark_poly::EvaluationDomain;
 use kimchi::circuits::lookup::runtime_tables::caml::CamlRuntimeTableCfg;
 use kimchi::circuits::lookup::runtime_tables::RuntimeTableCfg;
@@ -9,7 +14,7 @@ use kimchi::circuits::{constraints::ConstraintSystem, gate::CircuitGate};
 use kimchi::{linearization::expr_linearization, prover_index::ProverIndex};
 use mina_curves::pasta::{Fq, Pallas, PallasParameters, Vesta};
 use mina_poseidon::{constants::PlonkSpongeConstantsKimchi, sponge::DefaultFqSponge};
-use poly_commitment::{evaluation_proof::OpeningProof};
+use poly_commitment::{evaluation_proof::OpeningProof, SRS as _};
 use serde::{Deserialize, Serialize};
 use std::{
     fs::{File, OpenOptions},
@@ -40,6 +45,101 @@ impl ocaml::custom::Custom for CamlPastaFqPlonkIndex {
     };
 }
 
+/// Builds the constraint system for `gates`.
+pub fn create_constraint_system(
+    gates: Vec<CircuitGate<Fq>>,
+    public: usize,
+    lookup_tables: Vec<LookupTable<Fq>>,
+    runtime_tables: Vec<RuntimeTableCfg<Fq>>,
+    prev_challenges: usize,
+    srs: &CamlFqSrs,
+) -> Result<ConstraintSystem<Fq>, CamlSetupError> {
+    check_setup(&gates, public, &lookup_tables, &runtime_tables)?;
+
+    let cs = ConstraintSystem::<Fq>::create(gates)
+        .public(public)
+        .prev_challenges(prev_challenges)
+        .lookup(lookup_tables)
+        .runtime(if runtime_tables.is_empty() {
+            None
+        } else {
+            Some(runtime_tables)
+        })
+        .build()?;
+
+    // wrap circuits are not chunked, so their domain must fit in the SRS
+    check_domain(&cs, srs.0.max_poly_size())?;
+    Ok(cs)
+}
+
+/// Builds the prover index for `cs`, and caches its verifier index digest.
+/// The SRS must already contain the Lagrange basis for `cs`, and keep it readable
+/// (see [crate::srs::lagrange_basis]).
+pub fn create_prover_index(
+    cs: ConstraintSystem<Fq>,
+    srs: &CamlFqSrs,
+) -> ProverIndex<Pallas, OpeningProof<Pallas>> {
+    // endo
+    let (endo_q, _endo_r) = poly_commitment::srs::endos::<Vesta>();
+
+    // create index
+    let mut index = ProverIndex::<Pallas, OpeningProof<Pallas>>::create(cs, endo_q, srs.0.clone());
+    // Compute and cache the verifier index digest
+    index.compute_verifier_index_digest::<DefaultFqSponge<PallasParameters, PlonkSpongeConstantsKimchi>>();
+
+    index
+}
+
+/// Builds the constraint system and the prover index for `gates`.
+/// Adding the Lagrange basis to the SRS may wait for the threads that read it.
+pub fn create_index(
+    gates: Vec<CircuitGate<Fq>>,
+    public: usize,
+    lookup_tables: Vec<LookupTable<Fq>>,
+    runtime_tables: Vec<RuntimeTableCfg<Fq>>,
+    prev_challenges: usize,
+    srs: &CamlFqSrs,
+) -> Result<ProverIndex<Pallas, OpeningProof<Pallas>>, CamlSetupError> {
+    let cs = create_constraint_system(
+        gates,
+        public,
+        lookup_tables,
+        runtime_tables,
+        prev_challenges,
+        srs,
+    )?;
+    let _bases = crate::srs::lagrange_basis(&srs.0, cs.domain.d1);
+    Ok(create_prover_index(cs, srs))
+}
+
+/// Same as [create_index], but releases the OCaml runtime lock during the heavy work.
+fn create_index_releasing_runtime(
+    gates: Vec<CircuitGate<Fq>>,
+    public: usize,
+    lookup_tables: Vec<LookupTable<Fq>>,
+    runtime_tables: Vec<RuntimeTableCfg<Fq>>,
+    prev_challenges: usize,
+    srs: &CamlFqSrs,
+) -> Result<CamlPastaFqPlonkIndex, CamlSetupError> {
+    let index = crate::srs::releasing_runtime(|| -> Result<_, CamlSetupError> {
+        let cs = crate::prover_pool::install(|| {
+            create_constraint_system(
+                gates,
+                public,
+                lookup_tables,
+                runtime_tables,
+                prev_challenges,
+                srs,
+            )
+        })?;
+        // added outside the prover pool, since it may wait for the proofs in the pool
+        let _bases = crate::srs::lagrange_basis(&srs.0, cs.domain.d1);
+        Ok(crate::prover_pool::install(|| create_prover_index(cs, srs)))
+    })?;
+
+    Ok(CamlPastaFqPlonkIndex(Box::new(index)))
+}
+
 #[ocaml_gen::func]
 #[ocaml::func]
 pub fn caml_pasta_fq_plonk_index_create(
@@ -66,40 +166,76 @@ pub fn caml_pasta_fq_plonk_index_create(
 
     let lookup_tables: Vec<LookupTable<Fq>> = lookup_tables.into_iter().map(Into::into).collect();
 
-    // create constraint system
-    let cs = match ConstraintSystem::<Fq>::create(gates)
-        .public(public as usize)
-        .prev_challenges(prev_challenges as usize)
-        .lookup(lookup_tables)
-        .runtime(if runtime_tables.is_empty() {
-            None
-        } else {
-            Some(runtime_tables)
-        })
-        .build()
-    {
-        Err(e) => {
-            return Err(e.into())
-        }
-        Ok(cs) => cs,
-    };
+    create_index_releasing_runtime(
+        gates,
+        public as usize,
+        lookup_tables,
+        runtime_tables,
+        prev_challenges as usize,
+        &srs,
+    )
+    .map_err(Into::into)
+}
 
-    // endo
-    let (endo_q, _endo_r) = poly_commitment::srs::endos::<Vesta>();
+/// Same as `caml_pasta_fq_plonk_index_create`, but returns
+/// a [CamlSetupError] instead of raising an exception.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_index_try_create(
+    gates: CamlPastaFqPlonkGateVectorPtr,
+    public: ocaml::Int,
+    lookup_tables: Vec<CamlLookupTable<CamlFq>>,
+    runtime_tables: Vec<CamlRuntimeTableCfg<CamlFq>>,
+    prev_challenges: ocaml::Int,
+    srs: CamlFqSrs,
+) -> CamlResult<CamlPastaFqPlonkIndex, CamlSetupError> {
+    let gates: Vec<_> = gates.as_ref().0.clone();
 
-    // Unsafe if we are in a multi-core ocaml
-    {
-        let ptr: &mut poly_commitment::srs::SRS<Pallas> =
-            unsafe { &mut *(std::sync::Arc::as_ptr(&srs.0) as *mut _) };
-        ptr.add_lagrange_basis(cs.domain.d1);
-    }
+    let runtime_tables: Vec<RuntimeTableCfg<Fq>> =
+        runtime_tables.into_iter().map(Into::into).collect();
 
-    // create index
-    let mut index = ProverIndex::<Pallas, OpeningProof<Pallas>>::create(cs, endo_q, srs.clone());
-    // Compute and cache the verifier index digest
-    index.compute_verifier_index_digest::<DefaultFqSponge<PallasParameters, PlonkSpongeConstantsKimchi>>();
+    let lookup_tables: Vec<LookupTable<Fq>> = lookup_tables.into_iter().map(Into::into).collect();
 
-    Ok(CamlPastaFqPlonkIndex(Box::new(index)))
+    create_index_releasing_runtime(
+        gates,
+        public as usize,
+        lookup_tables,
+        runtime_tables,
+        prev_challenges as usize,
+        &srs,
+    )
+    .into()
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_index_create_from_circuit_json(
+    json: String,
+    lookup_tables: Vec<CamlLookupTable<CamlFq>>,
+    runtime_tables: Vec<CamlRuntimeTableCfg<CamlFq>>,
+    prev_challenges: ocaml::Int,
+    srs: CamlFqSrs,
+) -> Result<CamlPastaFqPlonkIndex, ocaml::Error> {
+    let circuit = SerializedCircuit::<Fq>::from_json(&json).map_err(|_| {
+        ocaml::Error::invalid_argument("caml_pasta_fq_plonk_index_create_from_circuit_json")
+            .err()
+            .unwrap()
+    })?;
+
+    let runtime_tables: Vec<RuntimeTableCfg<Fq>> =
+        runtime_tables.into_iter().map(Into::into).collect();
+
+    let lookup_tables: Vec<LookupTable<Fq>> = lookup_tables.into_iter().map(Into::into).collect();
+
+    create_index_releasing_runtime(
+        circuit.gates,
+        circuit.public_input_size,
+        lookup_tables,
+        runtime_tables,
+        prev_challenges as usize,
+        &srs,
+    )
+    .map_err(Into::into)
 }
 
 #[ocaml_gen::func]
@@ -1,8 +1,15 @@
 use crate::{
-    arkworks::{CamlFq, CamlGPallas},
+    arkworks::{CamlFp, CamlFq, CamlGPallas},
+    caml::caml_bytes_string::{CamlBytes, CamlBytesString},
+    dummy::{CamlDummyShape, DummyShape},
     pasta_fq_plonk_index::CamlPastaFqPlonkIndexPtr,
-    plonk_verifier_index::{CamlPlonkDomain, CamlPlonkVerificationEvals, CamlPlonkVerifierIndex},
+    plonk_verifier_index::{
+        verifier_index_of_bytes, verifier_index_of_json, verifier_index_to_bytes,
+        verifier_index_to_json, CamlPlonkDomain, CamlPlonkVerificationEvals,
+        CamlPlonkVerifierIndex,
+    },
     srs::fq::CamlFqSrs,
+    verifier_index_diff::{diff_verifier_indexes, CamlVerifierIndexChange},
 };
 use ark_ec::AffineCurve;
 use ark_ff::One;
@@ -13,9 +20,11 @@ use kimchi::circuits::polynomials::permutation::Shifts;
 use kimchi::circuits::polynomials::permutation::{permutation_vanishing_polynomial, zk_w};
 use kimchi::circuits::wires::{COLUMNS, PERMUTS};
 use kimchi::{linearization::expr_linearization, verifier_index::VerifierIndex};
-use mina_curves::pasta::{Fq, Pallas, Vesta};
+use mina_curves::pasta::{Fq, Pallas, PallasParameters, Vesta};
+use mina_poseidon::{constants::PlonkSpongeConstantsKimchi, sponge::DefaultFqSponge};
 use poly_commitment::{commitment::caml::CamlPolyComm, evaluation_proof::OpeningProof};
 use poly_commitment::{commitment::PolyComm, srs::SRS};
+use sha2::{Digest, Sha256};
 use std::convert::TryInto;
 use std::path::Path;
 use std::sync::Arc;
@@ -215,20 +224,129 @@ pub fn caml_pasta_fq_plonk_verifier_index_write(
     })
 }
 
+/// Encodes the verifier index without its SRS, see [verifier_index_to_bytes].
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_to_bytes(
+    index: CamlPastaFqPlonkVerifierIndex,
+) -> Result<CamlBytes, ocaml::Error> {
+    let index: VerifierIndex<Pallas, OpeningProof<Pallas>> = index.into();
+    verifier_index_to_bytes(&index, "fq")
+        .map(CamlBytes)
+        .map_err(ocaml::Error::Message)
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_of_bytes(
+    srs: CamlFqSrs,
+    bytes: CamlBytesString,
+) -> Result<CamlPastaFqPlonkVerifierIndex, ocaml::Error> {
+    verifier_index_of_bytes(bytes.0, "fq", srs.0)
+        .map(Into::into)
+        .map_err(ocaml::Error::Message)
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_to_json(
+    index: CamlPastaFqPlonkVerifierIndex,
+) -> Result<String, ocaml::Error> {
+    let index: VerifierIndex<Pallas, OpeningProof<Pallas>> = index.into();
+    verifier_index_to_json(index).map_err(ocaml::Error::Message)
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_of_json(
+    srs: CamlFqSrs,
+    json: String,
+) -> Result<CamlPastaFqPlonkVerifierIndex, ocaml::Error> {
+    verifier_index_of_json(&json, srs.0)
+        .map(Into::into)
+        .map_err(ocaml::Error::Message)
+}
+
+/// The Poseidon digest of the verifier index, the same as the one that
+/// the prover index caches in `compute_verifier_index_digest`.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_digest(index: CamlPastaFqPlonkVerifierIndex) -> CamlFp {
+    let index: VerifierIndex<Pallas, OpeningProof<Pallas>> = index.into();
+    index
+        .digest::<DefaultFqSponge<PallasParameters, PlonkSpongeConstantsKimchi>>()
+        .into()
+}
+
+/// The SHA-256 hash of the bytes returned by `to_bytes`, with nothing prepended.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_bytes_digest(
+    index: CamlPastaFqPlonkVerifierIndex,
+) -> Result<[u8; 32], ocaml::Error> {
+    let index: VerifierIndex<Pallas, OpeningProof<Pallas>> = index.into();
+    let bytes = verifier_index_to_bytes(&index, "fq").map_err(ocaml::Error::Message)?;
+    Ok(Sha256::digest(bytes).into())
+}
+
+/// The fields of `after` that differ from those of `before`, see [diff_verifier_indexes].
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_diff(
+    before: CamlPastaFqPlonkVerifierIndex,
+    after: CamlPastaFqPlonkVerifierIndex,
+) -> Vec<CamlVerifierIndexChange> {
+    let before: VerifierIndex<Pallas, OpeningProof<Pallas>> = before.into();
+    let after: VerifierIndex<Pallas, OpeningProof<Pallas>> = after.into();
+    diff_verifier_indexes(&before, &after)
+}
+
 #[ocaml_gen::func]
 #[ocaml::func]
 pub fn caml_pasta_fq_plonk_verifier_index_create(
     index: CamlPastaFqPlonkIndexPtr,
 ) -> CamlPastaFqPlonkVerifierIndex {
-    {
-        let ptr: &mut poly_commitment::srs::SRS<Pallas> =
-            unsafe { &mut *(std::sync::Arc::as_ptr(&index.as_ref().0.srs) as *mut _) };
-        ptr.add_lagrange_basis(index.as_ref().0.cs.domain.d1);
-    }
-    let verifier_index = index.as_ref().0.verifier_index();
+    let index = &index.as_ref().0;
+    let verifier_index = crate::srs::releasing_runtime(|| {
+        let _bases = crate::srs::lagrange_basis(&index.srs, index.cs.domain.d1);
+        index.verifier_index()
+    });
     verifier_index.into()
 }
 
+impl_shared_reference!(CamlPastaFqPlonkVerifierIndexHandle => VerifierIndex<Pallas, OpeningProof<Pallas>>);
+
+/// Creates a verifier index that stays on the Rust heap, so that it is not
+/// converted again by every function that uses it.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_create_handle(
+    index: CamlPastaFqPlonkIndexPtr,
+) -> CamlPastaFqPlonkVerifierIndexHandle {
+    let index = &index.as_ref().0;
+    let verifier_index = crate::srs::releasing_runtime(|| {
+        let _bases = crate::srs::lagrange_basis(&index.srs, index.cs.domain.d1);
+        index.verifier_index()
+    });
+    CamlPastaFqPlonkVerifierIndexHandle::new(verifier_index)
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_handle_of_index(
+    index: CamlPastaFqPlonkVerifierIndex,
+) -> CamlPastaFqPlonkVerifierIndexHandle {
+    CamlPastaFqPlonkVerifierIndexHandle::new(index.into())
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_handle_to_index(
+    handle: CamlPastaFqPlonkVerifierIndexHandle,
+) -> CamlPastaFqPlonkVerifierIndex {
+    (**handle).clone().into()
+}
+
 #[ocaml_gen::func]
 #[ocaml::func]
 pub fn caml_pasta_fq_plonk_verifier_index_shifts(log2_size: ocaml::Int) -> Vec<CamlFq> {
@@ -282,6 +400,18 @@ pub fn caml_pasta_fq_plonk_verifier_index_dummy() -> CamlPastaFqPlonkVerifierInd
     }
 }
 
+/// Same as `dummy`, with the commitments of a circuit of the given shape
+/// (see [DummyShape::verifier_index]).
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_pasta_fq_plonk_verifier_index_dummy_with_shape(
+    shape: CamlDummyShape,
+    srs: CamlFqSrs,
+) -> Result<CamlPastaFqPlonkVerifierIndex, ocaml::Error> {
+    let shape: DummyShape = shape.try_into()?;
+    Ok(shape.verifier_index::<Pallas, _, _, _>(srs))
+}
+
 #[ocaml_gen::func]
 #[ocaml::func]
 pub fn caml_pasta_fq_plonk_verifier_index_deep_copy(
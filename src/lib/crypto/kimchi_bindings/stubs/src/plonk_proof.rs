@@ -0,0 +1,2014 @@
+//! The proof-related functions are identical for the Vesta/Fp (step) and
+//! Pallas/Fq (wrap) circuits, so we generate both from [impl_proof].
+
+use ark_ec::AffineCurve;
+use kimchi::{circuits::lookup::runtime_tables::caml::CamlRuntimeTable, proof::ProverProof};
+use poly_commitment::evaluation_proof::OpeningProof;
+use serde::{Deserialize, Serialize};
+
+/// The arguments of a single proof, for the batch prover:
+/// `W` is the field vector type of the witness columns, `F` the scalar field and `G` the curve.
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlProverInput<W, F, G> {
+    pub witness: Vec<W>,
+    pub runtime_tables: Vec<CamlRuntimeTable<F>>,
+    pub prev_challenges: Vec<F>,
+    pub prev_sgs: Vec<G>,
+}
+
+/// The JSON form of a proof, used by `*_plonk_proof_to_json` and `*_plonk_proof_of_json`:
+/// the public input as hexadecimal field elements, and the proof (commitments, evaluations,
+/// opening proof and previous challenges) as serialized by kimchi.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "ProverProof<G, OpeningProof<G>>: Serialize",
+    deserialize = "ProverProof<G, OpeningProof<G>>: Deserialize<'de>"
+))]
+pub struct ProofJson<G: AffineCurve> {
+    pub public_input: Vec<String>,
+    pub proof: ProverProof<G, OpeningProof<G>>,
+}
+
+/// The first bytes of a proof encoded by `*_plonk_proof_to_bytes`.
+pub const PROOF_ENCODING_MAGIC: &[u8; 4] = b"KPRF";
+
+/// The version of the encoding of proofs, to bump whenever the encoding changes.
+pub const PROOF_ENCODING_VERSION: u8 = 1;
+
+/// Implements the `caml_pasta_<name>_plonk_proof_*` functions, where:
+///
+/// * `$F`/`$CamlF` is the scalar field of the circuit, and `$OtherF` the other field of the cycle,
+/// * `$G`/`$CamlG` is the curve used to commit, and `$OtherG` the other curve of the cycle,
+/// * `$CurveParams` are the parameters of `$G` used by the Fq-sponge.
+macro_rules! impl_proof {
+    (
+        $name: ident,
+        $CamlF: ident,
+        $F: ident,
+        $OtherF: ident,
+        $CamlG: ident,
+        $G: ident,
+        $OtherG: ident,
+        $CurveParams: ident,
+        $CamlFVector: ident,
+        $CamlIndex: ident,
+        $CamlIndexPtr: ident,
+        $CamlVerifierIndex: ident,
+        $CamlVerifierIndexHandle: ident,
+        $CamlSrs: ident
+    ) => {
+        use ark_ec::AffineCurve;
+        use ark_ff::One;
+        use array_init::array_init;
+        use crate::caml::caml_bytes_string::{CamlBytes, CamlBytesString};
+        use crate::caml::caml_result::CamlResult;
+        use crate::proving_task::{CamlProvingPhase, CamlProvingTask};
+        use crate::srs::{LagrangeBases, LagrangeBasesGuard};
+        use crate::verify_error::{check_public_input_length, CamlVerifyError};
+        use crate::witness::{check_witness, check_witness_lengths, CamlUnsatisfiedWitness};
+        use groupmap::GroupMap;
+        use kimchi::verifier::verify;
+        use kimchi::{
+            circuits::lookup::runtime_tables::{caml::CamlRuntimeTable, RuntimeTable},
+            prover_index::ProverIndex,
+        };
+        use kimchi::{circuits::polynomial::COLUMNS, verifier::batch_verify};
+        use kimchi::{
+            proof::{
+                PointEvaluations, ProofEvaluations, ProverCommitments, ProverProof,
+                RecursionChallenge,
+            },
+            verifier::Context,
+        };
+        use kimchi::{prover::caml::CamlProofWithPublic, verifier_index::VerifierIndex};
+        use mina_poseidon::{
+            constants::PlonkSpongeConstantsKimchi,
+            sponge::{DefaultFqSponge, DefaultFrSponge},
+        };
+        use paste::paste;
+        use poly_commitment::commitment::{CommitmentCurve, PolyComm};
+        use poly_commitment::evaluation_proof::OpeningProof;
+        use std::array;
+        use std::convert::TryInto;
+
+        type EFqSponge = DefaultFqSponge<$CurveParams, PlonkSpongeConstantsKimchi>;
+        type EFrSponge = DefaultFrSponge<$F, PlonkSpongeConstantsKimchi>;
+
+        /// Adds the Lagrange basis for the domain of the index to its SRS if needed,
+        /// and keeps it readable while the prover runs (see [crate::srs::lagrange_basis]).
+        fn lagrange_basis(index: &ProverIndex<$G, OpeningProof<$G>>) -> LagrangeBasesGuard {
+            crate::srs::lagrange_basis(&index.srs, index.cs.domain.d1)
+        }
+
+        /// Same as [lagrange_basis], for the verifier, which uses the basis
+        /// to commit to the public input.
+        fn verifier_lagrange_basis(
+            index: &VerifierIndex<$G, OpeningProof<$G>>,
+        ) -> LagrangeBasesGuard {
+            crate::srs::lagrange_basis(index.srs(), index.domain)
+        }
+
+        /// Same as [verifier_lagrange_basis], for several verifier indexes.
+        fn verifier_lagrange_bases<'a>(
+            indexes: impl IntoIterator<Item = &'a VerifierIndex<$G, OpeningProof<$G>>>,
+        ) -> LagrangeBasesGuard {
+            indexes
+                .into_iter()
+                .fold(LagrangeBases::default(), |bases, index| {
+                    bases.with(index.srs(), index.domain)
+                })
+                .read()
+        }
+
+        /// Groups the flattened challenges of the previous proofs with their commitments.
+        fn recursion_challenges(
+            prev_challenges: Vec<$CamlF>,
+            prev_sgs: Vec<$CamlG>,
+        ) -> Vec<RecursionChallenge<$G>> {
+            if prev_challenges.is_empty() {
+                return Vec::new();
+            }
+            let challenges_per_sg = prev_challenges.len() / prev_sgs.len();
+            prev_sgs
+                .into_iter()
+                .map(Into::<$G>::into)
+                .enumerate()
+                .map(|(i, sg)| {
+                    let chals = prev_challenges[(i * challenges_per_sg)..(i + 1) * challenges_per_sg]
+                        .iter()
+                        .map(Into::<$F>::into)
+                        .collect();
+                    let comm = PolyComm::<$G> {
+                        unshifted: vec![sg],
+                        shifted: None,
+                    };
+                    RecursionChallenge { chals, comm }
+                })
+                .collect()
+        }
+
+        /// Checks the number and length of the witness columns, before they are copied or moved.
+        fn check_witness_shape(
+            index: &ProverIndex<$G, OpeningProof<$G>>,
+            witness: &[$CamlFVector],
+        ) -> Result<(), ocaml::Error> {
+            let lengths: Vec<usize> = witness.iter().map(|x| x.len()).collect();
+            check_witness_lengths(&index.cs, &lengths).map_err(|e| ocaml::Error::Error(e.into()))
+        }
+
+        /// Copies the witness columns out of the OCaml vectors.
+        fn witness_columns(
+            index: &ProverIndex<$G, OpeningProof<$G>>,
+            witness: Vec<$CamlFVector>,
+        ) -> Result<[Vec<$F>; COLUMNS], ocaml::Error> {
+            check_witness_shape(index, &witness)?;
+            let witness: Vec<Vec<_>> = witness.iter().map(|x| (*x.0).clone()).collect();
+            witness
+                .try_into()
+                .map_err(|_| ocaml::Error::Message("the witness should be a column of 15 vectors"))
+        }
+
+        /// The public input of a proof: the first cells of the first witness column.
+        fn witness_public_input(
+            index: &ProverIndex<$G, OpeningProof<$G>>,
+            witness: &[Vec<$F>; COLUMNS],
+        ) -> Vec<$F> {
+            witness[0][0..index.cs.public].to_vec()
+        }
+
+        /// Moves the witness columns out of the OCaml vectors, which are left empty.
+        fn take_witness_columns(
+            index: &ProverIndex<$G, OpeningProof<$G>>,
+            witness: Vec<$CamlFVector>,
+        ) -> Result<[Vec<$F>; COLUMNS], ocaml::Error> {
+            check_witness_shape(index, &witness)?;
+            let witness: Vec<Vec<_>> = witness
+                .into_iter()
+                .map(|mut x| std::mem::take(&mut *x))
+                .collect();
+            witness
+                .try_into()
+                .map_err(|_| ocaml::Error::Message("the witness should be a column of 15 vectors"))
+        }
+
+        /// Verifies the proofs as a batch, checking their public input lengths first.
+        fn verify_contexts(
+            group_map: &<$G as CommitmentCurve>::Map,
+            contexts: &[Context<$G, OpeningProof<$G>>],
+        ) -> Result<(), CamlVerifyError> {
+            for context in contexts {
+                check_public_input_length(
+                    context.verifier_index.public,
+                    context.public_input.len(),
+                )?;
+            }
+            batch_verify::<$G, EFqSponge, EFrSponge, OpeningProof<$G>>(group_map, contexts)
+                .map_err(Into::into)
+        }
+
+        /// The part of the verification of a proof that does not depend on the rest of
+        /// its batch: its oracles, and the evaluations left to the batched opening proof check.
+        type BatchedProof<'a> = poly_commitment::commitment::BatchEvaluationProof<
+            'a,
+            $G,
+            EFqSponge,
+            OpeningProof<$G>,
+        >;
+
+        fn clone_batched_proof<'a>(proof: &BatchedProof<'a>) -> BatchedProof<'a> {
+            poly_commitment::commitment::BatchEvaluationProof {
+                sponge: proof.sponge.clone(),
+                evaluations: proof.evaluations.clone(),
+                evaluation_points: proof.evaluation_points.clone(),
+                polyscale: proof.polyscale,
+                evalscale: proof.evalscale,
+                opening: proof.opening,
+                combined_inner_product: proof.combined_inner_product,
+            }
+        }
+
+        /// Finds the proofs of `batch` (with their positions) that fail the batched opening
+        /// proof check, by splitting the batch in halves, checked in parallel, until the failing
+        /// proofs are isolated. Returns their positions.
+        fn bisect_batch(
+            srs: &poly_commitment::srs::SRS<$G>,
+            group_map: &<$G as CommitmentCurve>::Map,
+            batch: &[(usize, BatchedProof)],
+        ) -> Vec<usize> {
+            if batch.is_empty() {
+                return vec![];
+            }
+            let mut proofs: Vec<_> = batch
+                .iter()
+                .map(|(_, proof)| clone_batched_proof(proof))
+                .collect();
+            if srs.verify::<EFqSponge, _>(group_map, &mut proofs, &mut rand::thread_rng()) {
+                return vec![];
+            }
+            if let [(i, _)] = batch {
+                return vec![*i];
+            }
+            let (left, right) = batch.split_at(batch.len() / 2);
+            let (mut invalid, right) = rayon::join(
+                || bisect_batch(srs, group_map, left),
+                || bisect_batch(srs, group_map, right),
+            );
+            invalid.extend(right);
+            invalid
+        }
+
+        /// Computes the oracles of each proof of `contexts`, in parallel, and splits the proofs
+        /// into the ones left to the batched opening proof check and the ones that already
+        /// failed, with why. Both keep the positions of the proofs.
+        fn batch_contexts<'a>(
+            contexts: &[Context<'a, $G, OpeningProof<$G>>],
+        ) -> (Vec<(usize, BatchedProof<'a>)>, Vec<(usize, CamlVerifyError)>) {
+            use rayon::prelude::*;
+
+            let batched: Vec<_> = contexts
+                .par_iter()
+                .map(|context| -> Result<BatchedProof<'a>, CamlVerifyError> {
+                    check_public_input_length(
+                        context.verifier_index.public,
+                        context.public_input.len(),
+                    )?;
+                    kimchi::verifier::to_batch::<$G, EFqSponge, EFrSponge, OpeningProof<$G>>(
+                        context.verifier_index,
+                        context.proof,
+                        context.public_input,
+                    )
+                    .map_err(Into::into)
+                })
+                .collect();
+            let mut valid = vec![];
+            let mut invalid = vec![];
+            for (i, proof) in batched.into_iter().enumerate() {
+                match proof {
+                    Ok(proof) => valid.push((i, proof)),
+                    Err(e) => invalid.push((i, e)),
+                }
+            }
+            (valid, invalid)
+        }
+
+        /// The first proof of a batch that does not verify, and why. The oracles of each proof
+        /// are computed once, and the opening proofs are bisected (see [bisect_batch]) against
+        /// the SRS of the first verifier index, as kimchi's `batch_verify` does.
+        /// Runs in the prover pool.
+        fn first_invalid_proof(
+            group_map: &<$G as CommitmentCurve>::Map,
+            contexts: &[Context<$G, OpeningProof<$G>>],
+        ) -> Option<(usize, CamlVerifyError)> {
+            let srs = contexts.first()?.verifier_index.srs();
+            let mut invalid = crate::prover_pool::install(|| {
+                let (valid, mut invalid) = batch_contexts(contexts);
+                invalid.extend(
+                    bisect_batch(srs, group_map, &valid)
+                        .into_iter()
+                        .map(|i| (i, CamlVerifyError::OpeningProofFailed)),
+                );
+                invalid
+            });
+            invalid.sort_unstable_by_key(|(i, _)| *i);
+            invalid.into_iter().next()
+        }
+
+        /// Verifies the proofs as a batch, and if it fails, finds the first one that does not
+        /// verify (see [first_invalid_proof]). The runtime must be released.
+        fn verify_contexts_result(
+            group_map: &<$G as CommitmentCurve>::Map,
+            contexts: &[Context<$G, OpeningProof<$G>>],
+        ) -> CamlResult<(), (ocaml::Int, CamlVerifyError)> {
+            if verify_contexts(group_map, contexts).is_ok() {
+                return CamlResult::Ok(());
+            }
+            match first_invalid_proof(group_map, contexts) {
+                Some((i, e)) => CamlResult::Error((i as isize, e)),
+                // only the batch fails, we cannot blame a single proof
+                None => CamlResult::Error((
+                    0,
+                    CamlVerifyError::Other(
+                        "the batch does not verify, but each proof does".to_string(),
+                    ),
+                )),
+            }
+        }
+
+        paste! {
+            /// Encodes a proof and its public input as:
+            ///
+            /// * the header of [crate::encoding], with [crate::plonk_proof::PROOF_ENCODING_MAGIC],
+            /// * the public input, with arkworks' canonical serialization,
+            /// * the proof, as MessagePack with structs encoded as arrays, in field order,
+            ///   and points compressed by the arkworks serialization of `ProverProof`.
+            pub fn [<$name _proof_to_bytes>](
+                proof: &ProverProof<$G, OpeningProof<$G>>,
+                public_input: &[$F],
+            ) -> Result<Vec<u8>, &'static str> {
+                let mut bytes = vec![];
+                crate::encoding::encode_header(
+                    &mut bytes,
+                    crate::plonk_proof::PROOF_ENCODING_MAGIC,
+                    crate::plonk_proof::PROOF_ENCODING_VERSION,
+                    stringify!($name),
+                );
+                ark_serialize::CanonicalSerialize::serialize(&public_input.to_vec(), &mut bytes)
+                    .map_err(|_| "could not encode the public input")?;
+                serde::Serialize::serialize(proof, &mut rmp_serde::Serializer::new(&mut bytes))
+                    .map_err(|_| "could not encode the proof")?;
+                Ok(bytes)
+            }
+
+            /// Decodes a proof encoded by the function above. The decoding is strict:
+            /// points must be on the curve, field elements canonical, and the bytes must be
+            /// exactly the encoding of the decoded proof.
+            pub fn [<$name _proof_of_bytes>](
+                bytes: &[u8],
+            ) -> Result<(ProverProof<$G, OpeningProof<$G>>, Vec<$F>), &'static str> {
+                use ark_serialize::CanonicalDeserialize;
+
+                let mut rest = crate::encoding::decode_header(
+                    bytes,
+                    crate::plonk_proof::PROOF_ENCODING_MAGIC,
+                    crate::plonk_proof::PROOF_ENCODING_VERSION,
+                    stringify!($name),
+                )?;
+                let public_input = <Vec<$F> as CanonicalDeserialize>::deserialize(&mut rest)
+                    .map_err(|_| "could not decode the public input")?;
+                let proof = <ProverProof<$G, OpeningProof<$G>> as serde::Deserialize>::deserialize(
+                    &mut rmp_serde::Deserializer::new(&mut rest),
+                )
+                .map_err(|_| "could not decode the proof")?;
+                if !rest.is_empty() {
+                    return Err("trailing bytes after the proof");
+                }
+
+                // rejects the alternative encodings that MessagePack accepts
+                if [<$name _proof_to_bytes>](&proof, &public_input)? != bytes {
+                    return Err("the proof is not canonically encoded");
+                }
+                Ok((proof, public_input))
+            }
+
+            /// The JSON form of a proof, see [crate::plonk_proof::ProofJson].
+            pub fn [<$name _proof_to_json>](
+                proof: ProverProof<$G, OpeningProof<$G>>,
+                public_input: &[$F],
+            ) -> Result<String, &'static str> {
+                use o1_utils::FieldHelpers;
+
+                let json = crate::plonk_proof::ProofJson {
+                    public_input: public_input.iter().map(|x| x.to_hex()).collect(),
+                    proof,
+                };
+                serde_json::to_string(&json).map_err(|_| "could not serialize the proof")
+            }
+
+            /// Parses a proof from the function above.
+            pub fn [<$name _proof_of_json>](
+                json: &str,
+            ) -> Result<(ProverProof<$G, OpeningProof<$G>>, Vec<$F>), String> {
+                use o1_utils::FieldHelpers;
+
+                let json: crate::plonk_proof::ProofJson<$G> = serde_json::from_str(json)
+                    .map_err(|e| format!("could not parse the proof: {}", e))?;
+                let public_input = json
+                    .public_input
+                    .iter()
+                    .map(|x| $F::from_hex(x))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| format!("could not parse the public input: {}", e))?;
+                Ok((json.proof, public_input))
+            }
+
+            // NB: proofs cannot be made reproducible from here. Besides the witness blinders
+            // (the last argument of `create_recursive`), kimchi draws the zero-knowledge rows,
+            // the quotient blinders and the opening proof randomness from `OsRng` internally.
+            // A seeded mode needs `create_recursive` to take the RNG as an argument first.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_create>](
+                index: $CamlIndexPtr<'static>,
+                witness: Vec<$CamlFVector>,
+                runtime_tables: Vec<CamlRuntimeTable<$CamlF>>,
+                prev_challenges: Vec<$CamlF>,
+                prev_sgs: Vec<$CamlG>,
+            ) -> Result<CamlProofWithPublic<$CamlG, $CamlF>, ocaml::Error> {
+                let index: &ProverIndex<$G, OpeningProof<$G>> = &index.as_ref().0;
+                let prev = recursion_challenges(prev_challenges, prev_sgs);
+                let witness = witness_columns(index, witness)?;
+                let runtime_tables: Vec<RuntimeTable<$F>> =
+                    runtime_tables.into_iter().map(Into::into).collect();
+
+                let public_input = witness_public_input(index, &witness);
+
+                // Release the runtime lock so that other threads can run using it while we generate the proof.
+                crate::srs::releasing_runtime(|| {
+                    let _bases = lagrange_basis(index);
+                    let group_map = GroupMap::<$OtherF>::setup();
+                    let proof = crate::prover_pool::prove(1, || {
+                        ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                            &group_map,
+                            witness,
+                            &runtime_tables,
+                            index,
+                            prev,
+                            None,
+                        )
+                    })
+                    .map_err(|e| ocaml::Error::Error(e.into()))?;
+                    Ok((proof, public_input).into())
+                })
+            }
+
+            /// Same as `create`, but moves the witness out of the vectors instead of copying it:
+            /// the vectors are empty after this call, even if the proof fails.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_create_owned>](
+                index: $CamlIndexPtr<'static>,
+                witness: Vec<$CamlFVector>,
+                runtime_tables: Vec<CamlRuntimeTable<$CamlF>>,
+                prev_challenges: Vec<$CamlF>,
+                prev_sgs: Vec<$CamlG>,
+            ) -> Result<CamlProofWithPublic<$CamlG, $CamlF>, ocaml::Error> {
+                let index: &ProverIndex<$G, OpeningProof<$G>> = &index.as_ref().0;
+                let prev = recursion_challenges(prev_challenges, prev_sgs);
+                let witness = take_witness_columns(index, witness)?;
+                let runtime_tables: Vec<RuntimeTable<$F>> =
+                    runtime_tables.into_iter().map(Into::into).collect();
+
+                let public_input = witness_public_input(index, &witness);
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = lagrange_basis(index);
+                    let group_map = GroupMap::<$OtherF>::setup();
+                    let proof = crate::prover_pool::prove(1, || {
+                        ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                            &group_map,
+                            witness,
+                            &runtime_tables,
+                            index,
+                            prev,
+                            None,
+                        )
+                    })
+                    .map_err(|e| ocaml::Error::Error(e.into()))?;
+                    Ok((proof, public_input).into())
+                })
+            }
+
+            /// Same as `create`, but first checks the witness against the circuit,
+            /// and returns the first row that is not satisfied instead of a proof.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_create_debug>](
+                index: $CamlIndexPtr<'static>,
+                witness: Vec<$CamlFVector>,
+                runtime_tables: Vec<CamlRuntimeTable<$CamlF>>,
+                prev_challenges: Vec<$CamlF>,
+                prev_sgs: Vec<$CamlG>,
+            ) -> Result<
+                CamlResult<CamlProofWithPublic<$CamlG, $CamlF>, CamlUnsatisfiedWitness<$CamlF>>,
+                ocaml::Error,
+            > {
+                let index: &ProverIndex<$G, OpeningProof<$G>> = &index.as_ref().0;
+                let prev = recursion_challenges(prev_challenges, prev_sgs);
+                let witness = witness_columns(index, witness)?;
+                let runtime_tables: Vec<RuntimeTable<$F>> =
+                    runtime_tables.into_iter().map(Into::into).collect();
+
+                let public_input = witness_public_input(index, &witness);
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = lagrange_basis(index);
+                    let gate_check = index.verify(&witness, &public_input);
+                    if let Some(failure) =
+                        check_witness(&index.cs, &witness, &runtime_tables, gate_check)
+                    {
+                        return Ok(CamlResult::Error(failure.into_caml(&index.cs, &witness)));
+                    }
+
+                    let group_map = GroupMap::<$OtherF>::setup();
+                    let proof = crate::prover_pool::prove(1, || {
+                        ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                            &group_map,
+                            witness,
+                            &runtime_tables,
+                            index,
+                            prev,
+                            None,
+                        )
+                    })
+                    .map_err(|e| ocaml::Error::Error(e.into()))?;
+                    Ok(CamlResult::Ok((proof, public_input).into()))
+                })
+            }
+
+            /// Same as `create`, but reports its phase to `task`, and returns `None` if the
+            /// task was cancelled before the proof was finished. A task tracks a single proof,
+            /// and ends in `Failed` if the witness or the prover fails. A cancellation does
+            /// not stop the kimchi prover once it has started, see [crate::proving_task].
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_create_with_task>](
+                index: $CamlIndexPtr<'static>,
+                witness: Vec<$CamlFVector>,
+                runtime_tables: Vec<CamlRuntimeTable<$CamlF>>,
+                prev_challenges: Vec<$CamlF>,
+                prev_sgs: Vec<$CamlG>,
+                task: CamlProvingTask,
+            ) -> Result<Option<CamlProofWithPublic<$CamlG, $CamlF>>, ocaml::Error> {
+                if !task.start().map_err(ocaml::Error::Message)? {
+                    return Ok(None);
+                }
+                let index: &ProverIndex<$G, OpeningProof<$G>> = &index.as_ref().0;
+                let prev = recursion_challenges(prev_challenges, prev_sgs);
+                let witness = witness_columns(index, witness).map_err(|e| task.failed(e))?;
+                let runtime_tables: Vec<RuntimeTable<$F>> =
+                    runtime_tables.into_iter().map(Into::into).collect();
+
+                let public_input = witness_public_input(index, &witness);
+
+                let proof = crate::srs::releasing_runtime(|| {
+                    let _bases = lagrange_basis(index);
+                    let group_map = GroupMap::<$OtherF>::setup();
+                    if !task.enter(CamlProvingPhase::Proving) {
+                        return Ok(None);
+                    }
+                    crate::prover_pool::prove(1, || {
+                        ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                            &group_map,
+                            witness,
+                            &runtime_tables,
+                            index,
+                            prev,
+                            None,
+                        )
+                    })
+                    .map(Some)
+                    .map_err(|e| task.failed(ocaml::Error::Error(e.into())))
+                })?;
+
+                match proof {
+                    Some(proof) if task.enter(CamlProvingPhase::Finishing) => {
+                        let proof = (proof, public_input).into();
+                        task.done();
+                        Ok(Some(proof))
+                    }
+                    _ => Ok(None),
+                }
+            }
+
+            /// Creates one proof per input, all against `index`, in parallel in the prover pool
+            /// (see [crate::prover_pool]). The results are in the order of the inputs.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_create_batch>](
+                index: $CamlIndexPtr<'static>,
+                inputs: Vec<crate::plonk_proof::CamlProverInput<$CamlFVector, $CamlF, $CamlG>>,
+            ) -> Result<Vec<CamlResult<CamlProofWithPublic<$CamlG, $CamlF>, String>>, ocaml::Error> {
+                let index: &ProverIndex<$G, OpeningProof<$G>> = &index.as_ref().0;
+
+                // copy everything out of the OCaml heap before releasing the runtime
+                let inputs = inputs
+                    .into_iter()
+                    .map(|input| {
+                        let witness = witness_columns(index, input.witness)?;
+                        let runtime_tables: Vec<RuntimeTable<$F>> =
+                            input.runtime_tables.into_iter().map(Into::into).collect();
+                        let prev = recursion_challenges(input.prev_challenges, input.prev_sgs);
+                        Ok((witness, runtime_tables, prev))
+                    })
+                    .collect::<Result<Vec<_>, ocaml::Error>>()?;
+
+                let proofs = crate::srs::releasing_runtime(|| {
+                    let _bases = lagrange_basis(index);
+                    use rayon::prelude::*;
+
+                    let group_map = GroupMap::<$OtherF>::setup();
+                    let chunk_size = crate::prover_pool::max_concurrent_proofs()
+                        .unwrap_or(inputs.len())
+                        .max(1);
+                    let mut inputs = inputs.into_iter();
+                    let mut proofs = Vec::with_capacity(inputs.len());
+                    loop {
+                        // bound the number of proofs in memory at once
+                        let chunk: Vec<_> = inputs.by_ref().take(chunk_size).collect();
+                        if chunk.is_empty() {
+                            break;
+                        }
+                        crate::prover_pool::prove(chunk.len(), || {
+                            proofs.par_extend(chunk.into_par_iter().map(
+                                |(witness, runtime_tables, prev)| {
+                                    let public_input = witness_public_input(index, &witness);
+                                    ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                                        &group_map,
+                                        witness,
+                                        &runtime_tables,
+                                        index,
+                                        prev,
+                                        None,
+                                    )
+                                    .map(|proof| (proof, public_input))
+                                    .map_err(|e| e.to_string())
+                                },
+                            ))
+                        });
+                    }
+                    proofs
+                });
+
+                Ok(proofs
+                    .into_iter()
+                    .map(|res| res.map(Into::into).into())
+                    .collect())
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_create_and_verify>](
+                index: $CamlIndexPtr<'static>,
+                witness: Vec<$CamlFVector>,
+                runtime_tables: Vec<CamlRuntimeTable<$CamlF>>,
+                prev_challenges: Vec<$CamlF>,
+                prev_sgs: Vec<$CamlG>,
+            ) -> Result<CamlProofWithPublic<$CamlG, $CamlF>, ocaml::Error> {
+                let index: &ProverIndex<$G, OpeningProof<$G>> = &index.as_ref().0;
+                let prev = recursion_challenges(prev_challenges, prev_sgs);
+                let witness = witness_columns(index, witness)?;
+                let runtime_tables: Vec<RuntimeTable<$F>> =
+                    runtime_tables.into_iter().map(Into::into).collect();
+
+                let public_input = witness_public_input(index, &witness);
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = lagrange_basis(index);
+                    let group_map = GroupMap::<$OtherF>::setup();
+                    let proof = crate::prover_pool::prove(1, || {
+                        ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                            &group_map,
+                            witness,
+                            &runtime_tables,
+                            index,
+                            prev,
+                            None,
+                        )
+                    })
+                    .map_err(|e| ocaml::Error::Error(e.into()))?;
+
+                    let verifier_index = index.verifier_index();
+
+                    // Verify proof
+                    verify::<$G, EFqSponge, EFrSponge, OpeningProof<$G>>(
+                        &group_map,
+                        &verifier_index,
+                        &proof,
+                        &public_input,
+                    )?;
+
+                    Ok((proof, public_input).into())
+                })
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_example_with_lookup>](
+                srs: $CamlSrs,
+            ) -> (
+                $CamlIndex,
+                $CamlF,
+                CamlProofWithPublic<$CamlG, $CamlF>,
+            ) {
+                use ark_ff::Zero;
+                use kimchi::circuits::{
+                    constraints::ConstraintSystem,
+                    gate::{CircuitGate, GateType},
+                    lookup::runtime_tables::{RuntimeTable, RuntimeTableCfg},
+                    polynomial::COLUMNS,
+                    wires::Wire,
+                };
+                use poly_commitment::srs::endos;
+
+                let num_gates = 1000;
+                let num_tables = 5;
+
+                let mut runtime_tables_setup = vec![];
+                for table_id in 0..num_tables {
+                    let cfg = RuntimeTableCfg {
+                        id: table_id,
+                        first_column: [8u32, 9, 8, 7, 1].into_iter().map(Into::into).collect(),
+                    };
+                    runtime_tables_setup.push(cfg);
+                }
+
+                let data: Vec<$F> = [0u32, 2, 3, 4, 5].into_iter().map(Into::into).collect();
+                let runtime_tables: Vec<RuntimeTable<$F>> = runtime_tables_setup
+                    .iter()
+                    .map(|cfg| RuntimeTable {
+                        id: cfg.id(),
+                        data: data.clone(),
+                    })
+                    .collect();
+
+                // circuit
+                let mut gates = vec![];
+                for row in 0..num_gates {
+                    gates.push(CircuitGate {
+                        typ: GateType::Lookup,
+                        wires: Wire::for_row(row),
+                        coeffs: vec![],
+                    });
+                }
+
+                // witness
+                let witness = {
+                    let mut cols: [_; COLUMNS] = array_init(|_col| vec![$F::zero(); gates.len()]);
+
+                    // only the first 7 registers are used in the lookup gate
+                    let (lookup_cols, _rest) = cols.split_at_mut(7);
+
+                    for row in 0..num_gates {
+                        // the first register is the table id
+                        lookup_cols[0][row] = 0u32.into();
+
+                        // create queries into our runtime lookup table
+                        let lookup_cols = &mut lookup_cols[1..];
+                        for chunk in lookup_cols.chunks_mut(2) {
+                            chunk[0][row] = 9u32.into(); // index
+                            chunk[1][row] = 2u32.into(); // value
+                        }
+                    }
+                    cols
+                };
+
+                let num_public_inputs = 1;
+
+                // not sure if theres a smarter way instead of the double unwrap, but should be fine in the test
+                let cs = ConstraintSystem::<$F>::create(gates)
+                    .runtime(Some(runtime_tables_setup))
+                    .public(num_public_inputs)
+                    .build()
+                    .unwrap();
+
+                let _bases = crate::srs::releasing_runtime(|| {
+                    crate::srs::lagrange_basis(&srs.0, cs.domain.d1)
+                });
+
+                let (endo_q, _endo_r) = endos::<$OtherG>();
+                let index = ProverIndex::<$G, OpeningProof<$G>>::create(cs, endo_q, srs.0);
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+                let public_input = witness[0][0];
+                let proof = ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                    &group_map,
+                    witness,
+                    &runtime_tables,
+                    &index,
+                    vec![],
+                    None,
+                )
+                .unwrap();
+
+                let caml_prover_proof = (proof, vec![public_input]).into();
+
+                (
+                    $CamlIndex(Box::new(index)),
+                    public_input.into(),
+                    caml_prover_proof,
+                )
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_example_with_foreign_field_mul>](
+                srs: $CamlSrs,
+            ) -> (
+                $CamlIndex,
+                CamlProofWithPublic<$CamlG, $CamlF>,
+            ) {
+                use ark_ff::Zero;
+                use kimchi::circuits::{
+                    constraints::ConstraintSystem,
+                    gate::{CircuitGate, Connect},
+                    polynomials::foreign_field_mul,
+                    wires::Wire,
+                };
+                use num_bigint::BigUint;
+                use num_bigint::RandBigInt;
+                use o1_utils::{foreign_field::BigUintForeignFieldHelpers, FieldHelpers};
+                use poly_commitment::srs::endos;
+                use rand::{rngs::StdRng, SeedableRng};
+
+                let foreign_field_modulus = $OtherF::modulus_biguint();
+
+                // Layout
+                //      0-1  ForeignFieldMul | Zero
+                //      2-5  compact-multi-range-check (result range check)
+                //        6  "single" Generic (result bound)
+                //      7-10 multi-range-check (quotient range check)
+                //     11-14 multi-range-check (quotient_bound, product1_lo, product1_hi_0)
+                //     later limb-check result bound
+                //        15 Generic (left and right bounds)
+                //     16-19 multi-range-check (left multiplicand)
+                //     20-23 multi-range-check (right multiplicand)
+                //     24-27 multi-range-check (result bound, left bound, right bound)
+                // TODO: check when kimchi is merged to berkeley
+
+                // Create foreign field multiplication gates
+                let (mut next_row, mut gates) =
+                    CircuitGate::<$F>::create_foreign_field_mul(0, &foreign_field_modulus);
+
+                let rng = &mut StdRng::from_seed([2u8; 32]);
+                let left_input = rng.gen_biguint_range(&BigUint::zero(), &foreign_field_modulus);
+                let right_input = rng.gen_biguint_range(&BigUint::zero(), &foreign_field_modulus);
+
+                // Compute multiplication witness
+                let (mut witness, mut external_checks) =
+                    foreign_field_mul::witness::create(&left_input, &right_input, &foreign_field_modulus);
+
+                // Result compact-multi-range-check
+                CircuitGate::extend_compact_multi_range_check(&mut gates, &mut next_row);
+                gates.connect_cell_pair((1, 0), (4, 1)); // remainder01
+                gates.connect_cell_pair((1, 1), (2, 0)); // remainder2
+                external_checks.extend_witness_compact_multi_range_checks(&mut witness);
+                // These are the coordinates (row, col) of the remainder limbs in the witness
+                // remainder0 -> (3, 0), remainder1 -> (4, 0), remainder2 -> (2,0)
+
+                // Constant single Generic gate for result bound
+                CircuitGate::extend_high_bounds(&mut gates, &mut next_row, &foreign_field_modulus);
+                gates.connect_cell_pair((6, 0), (1, 1)); // remainder2
+                external_checks.extend_witness_high_bounds_computation(&mut witness, &foreign_field_modulus);
+
+                // Quotient multi-range-check
+                CircuitGate::extend_multi_range_check(&mut gates, &mut next_row);
+                gates.connect_cell_pair((1, 2), (7, 0)); // quotient0
+                gates.connect_cell_pair((1, 3), (8, 0)); // quotient1
+                gates.connect_cell_pair((1, 4), (9, 0)); // quotient2
+                                                         // Witness updated below
+
+                // Multiplication witness value quotient_bound, product1_lo, product1_hi_0 multi-range-check
+                CircuitGate::extend_multi_range_check(&mut gates, &mut next_row);
+                gates.connect_cell_pair((1, 5), (11, 0)); // quotient_bound
+                gates.connect_cell_pair((0, 6), (12, 0)); // product1_lo
+                gates.connect_cell_pair((1, 6), (13, 0)); // product1_hi_0
+                                                          // Witness updated below
+
+                // Add witness for external multi-range checks:
+                // [quotient0, quotient1, quotient2]
+                // [quotient_bound, product1_lo, product1_hi_0]
+                external_checks.extend_witness_multi_range_checks(&mut witness);
+
+                // DESIGNER CHOICE: left and right (and result bound from before)
+                let left_limbs = left_input.to_field_limbs();
+                let right_limbs = right_input.to_field_limbs();
+                // Constant Double Generic gate for result and quotient bounds
+                external_checks.add_high_bound_computation(&left_limbs[2]);
+                external_checks.add_high_bound_computation(&right_limbs[2]);
+                CircuitGate::extend_high_bounds(&mut gates, &mut next_row, &foreign_field_modulus);
+                gates.connect_cell_pair((15, 0), (0, 2)); // left2
+                gates.connect_cell_pair((15, 3), (0, 5)); // right2
+                external_checks.extend_witness_high_bounds_computation(&mut witness, &foreign_field_modulus);
+
+                // Left input multi-range-check
+                external_checks.add_multi_range_check(&left_limbs);
+                CircuitGate::extend_multi_range_check(&mut gates, &mut next_row);
+                gates.connect_cell_pair((0, 0), (16, 0)); // left_input0
+                gates.connect_cell_pair((0, 1), (17, 0)); // left_input1
+                gates.connect_cell_pair((0, 2), (18, 0)); // left_input2
+                                                          // Witness updated below
+
+                // Right input multi-range-check
+                external_checks.add_multi_range_check(&right_limbs);
+                CircuitGate::extend_multi_range_check(&mut gates, &mut next_row);
+                gates.connect_cell_pair((0, 3), (20, 0)); // right_input0
+                gates.connect_cell_pair((0, 4), (21, 0)); // right_input1
+                gates.connect_cell_pair((0, 5), (22, 0)); // right_input2
+                                                          // Witness updated below
+
+                // Add witness for external multi-range checks:
+                // left and right limbs
+                external_checks.extend_witness_multi_range_checks(&mut witness);
+
+                // [result_bound, 0, 0]
+                // Bounds for result limb range checks
+                CircuitGate::extend_multi_range_check(&mut gates, &mut next_row);
+                gates.connect_cell_pair((6, 2), (24, 0)); // result_bound
+                                                          // Witness updated below
+
+                // Multi-range check bounds for left and right inputs
+                let left_hi_bound =
+                    foreign_field_mul::witness::compute_high_bound(&left_input, &foreign_field_modulus);
+                let right_hi_bound =
+                    foreign_field_mul::witness::compute_high_bound(&right_input, &foreign_field_modulus);
+                external_checks.add_limb_check(&left_hi_bound.into());
+                external_checks.add_limb_check(&right_hi_bound.into());
+                gates.connect_cell_pair((15, 2), (25, 0)); // left_bound
+                gates.connect_cell_pair((15, 5), (26, 0)); // right_bound
+
+                external_checks.extend_witness_limb_checks(&mut witness);
+
+                // Temporary workaround for lookup-table/domain-size issue
+                for _ in 0..(1 << 13) {
+                    gates.push(CircuitGate::zero(Wire::for_row(next_row)));
+                    next_row += 1;
+                }
+
+                // Create constraint system
+                let cs = ConstraintSystem::<$F>::create(gates)
+                    .lookup(vec![foreign_field_mul::gadget::lookup_table()])
+                    .build()
+                    .unwrap();
+
+                let _bases = crate::srs::releasing_runtime(|| {
+                    crate::srs::lagrange_basis(&srs.0, cs.domain.d1)
+                });
+
+                let (endo_q, _endo_r) = endos::<$OtherG>();
+                let index = ProverIndex::<$G, OpeningProof<$G>>::create(cs, endo_q, srs.0);
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+                let proof = ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                    &group_map,
+                    witness,
+                    &[],
+                    &index,
+                    vec![],
+                    None,
+                )
+                .unwrap();
+                (
+                    $CamlIndex(Box::new(index)),
+                    (proof, vec![]).into(),
+                )
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_example_with_range_check>](
+                srs: $CamlSrs,
+            ) -> (
+                $CamlIndex,
+                CamlProofWithPublic<$CamlG, $CamlF>,
+            ) {
+                use ark_ff::Zero;
+                use kimchi::circuits::{
+                    constraints::ConstraintSystem, gate::CircuitGate, polynomials::range_check, wires::Wire,
+                };
+                use num_bigint::BigUint;
+                use num_bigint::RandBigInt;
+                use o1_utils::{foreign_field::BigUintForeignFieldHelpers, BigUintFieldHelpers};
+                use poly_commitment::srs::endos;
+                use rand::{rngs::StdRng, SeedableRng};
+
+                let rng = &mut StdRng::from_seed([255u8; 32]);
+
+                // Create range-check gadget
+                let (mut next_row, mut gates) = CircuitGate::<$F>::create_multi_range_check(0);
+
+                // Create witness
+                let witness = range_check::witness::create_multi::<$F>(
+                    rng.gen_biguint_range(&BigUint::zero(), &BigUint::two_to_limb())
+                        .to_field()
+                        .expect("failed to convert to field"),
+                    rng.gen_biguint_range(&BigUint::zero(), &BigUint::two_to_limb())
+                        .to_field()
+                        .expect("failed to convert to field"),
+                    rng.gen_biguint_range(&BigUint::zero(), &BigUint::two_to_limb())
+                        .to_field()
+                        .expect("failed to convert to field"),
+                );
+
+                // Temporary workaround for lookup-table/domain-size issue
+                for _ in 0..(1 << 13) {
+                    gates.push(CircuitGate::zero(Wire::for_row(next_row)));
+                    next_row += 1;
+                }
+
+                // Create constraint system
+                let cs = ConstraintSystem::<$F>::create(gates)
+                    .lookup(vec![range_check::gadget::lookup_table()])
+                    .build()
+                    .unwrap();
+
+                let _bases = crate::srs::releasing_runtime(|| {
+                    crate::srs::lagrange_basis(&srs.0, cs.domain.d1)
+                });
+
+                let (endo_q, _endo_r) = endos::<$OtherG>();
+                let index = ProverIndex::<$G, OpeningProof<$G>>::create(cs, endo_q, srs.0);
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+                let proof = ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                    &group_map,
+                    witness,
+                    &[],
+                    &index,
+                    vec![],
+                    None,
+                )
+                .unwrap();
+                (
+                    $CamlIndex(Box::new(index)),
+                    (proof, vec![]).into(),
+                )
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_example_with_range_check0>](
+                srs: $CamlSrs,
+            ) -> (
+                $CamlIndex,
+                CamlProofWithPublic<$CamlG, $CamlF>,
+            ) {
+                use ark_ff::Zero;
+                use kimchi::circuits::{
+                    constraints::ConstraintSystem,
+                    gate::{CircuitGate, Connect},
+                    polynomial::COLUMNS,
+                    polynomials::{generic::GenericGateSpec, range_check},
+                    wires::Wire,
+                };
+                use poly_commitment::srs::endos;
+
+                let gates = {
+                    // Public input row with value 0
+                    let mut gates = vec![CircuitGate::<$F>::create_generic_gadget(
+                        Wire::for_row(0),
+                        GenericGateSpec::Const($F::zero()),
+                        None,
+                    )];
+                    let mut row = 1;
+                    CircuitGate::<$F>::extend_range_check(&mut gates, &mut row);
+
+                    // Temporary workaround for lookup-table/domain-size issue
+                    for _ in 0..(1 << 13) {
+                        gates.push(CircuitGate::zero(Wire::for_row(gates.len())));
+                    }
+
+                    // Connect the zero row to the range-check row to check prefix are zeros
+                    gates.connect_64bit(0, 1);
+
+                    gates
+                };
+
+                // witness
+                let witness = {
+                    // create row for the zero value
+                    let mut witness: [_; COLUMNS] = array_init(|_col| vec![$F::zero(); 1]);
+                    // create row for the 64-bit value
+                    range_check::witness::extend_single(&mut witness, $F::from(2u128.pow(64) - 1));
+                    witness
+                };
+
+                // not sure if theres a smarter way instead of the double unwrap, but should be fine in the test
+                let cs = ConstraintSystem::<$F>::create(gates)
+                    .lookup(vec![range_check::gadget::lookup_table()])
+                    .build()
+                    .unwrap();
+
+                let _bases = crate::srs::releasing_runtime(|| {
+                    crate::srs::lagrange_basis(&srs.0, cs.domain.d1)
+                });
+
+                let (endo_q, _endo_r) = endos::<$OtherG>();
+                let index = ProverIndex::<$G, OpeningProof<$G>>::create(cs, endo_q, srs.0);
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+                let proof = ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                    &group_map,
+                    witness,
+                    &[],
+                    &index,
+                    vec![],
+                    None,
+                )
+                .unwrap();
+                (
+                    $CamlIndex(Box::new(index)),
+                    (proof, vec![]).into(),
+                )
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_example_with_ffadd>](
+                srs: $CamlSrs,
+            ) -> (
+                $CamlIndex,
+                $CamlF,
+                CamlProofWithPublic<$CamlG, $CamlF>,
+            ) {
+                use ark_ff::Zero;
+                use kimchi::circuits::{
+                    constraints::ConstraintSystem,
+                    gate::{CircuitGate, Connect},
+                    polynomial::COLUMNS,
+                    polynomials::{
+                        foreign_field_add::witness::{create_chain, FFOps},
+                        generic::GenericGateSpec,
+                        range_check,
+                    },
+                    wires::Wire,
+                };
+                use num_bigint::BigUint;
+                use poly_commitment::srs::endos;
+
+                // Includes a row to store value 1
+                let num_public_inputs = 1;
+                let operation = &[FFOps::Add];
+                let modulus = BigUint::from_bytes_be(&[
+                    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE, 0xFF, 0xFF, 0xFF, 0xFF,
+                    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE, 0xFF, 0xFF,
+                    0xFC, 0x2F,
+                ]);
+
+                // circuit
+                // [0]       -> Public input row to store the value 1
+                // [1]       -> 1 ForeignFieldAdd row
+                // [2]       -> 1 ForeignFieldAdd row for final bound
+                // [3]       -> 1 Zero row for bound result
+                // [4..=7]   -> 1 Multi RangeCheck for left input
+                // [8..=11]  -> 1 Multi RangeCheck for right input
+                // [12..=15] -> 1 Multi RangeCheck for result
+                // [16..=19] -> 1 Multi RangeCheck for bound check
+                let gates = {
+                    // Public input row
+                    let mut gates = vec![CircuitGate::<$F>::create_generic_gadget(
+                        Wire::for_row(0),
+                        GenericGateSpec::Pub,
+                        None,
+                    )];
+
+                    let mut curr_row = num_public_inputs;
+                    // Foreign field addition and bound check
+                    CircuitGate::<$F>::extend_chain_ffadd(&mut gates, 0, &mut curr_row, operation, &modulus);
+
+                    // Extend rangechecks of left input, right input, result, and bound
+                    for _ in 0..4 {
+                        CircuitGate::extend_multi_range_check(&mut gates, &mut curr_row);
+                    }
+                    // Connect the witnesses of the addition to the corresponding range checks
+                    gates.connect_ffadd_range_checks(1, Some(4), Some(8), 12);
+                    // Connect the bound check range checks
+                    gates.connect_ffadd_range_checks(2, None, None, 16);
+
+                    // Temporary workaround for lookup-table/domain-size issue
+                    for _ in 0..(1 << 13) {
+                        gates.push(CircuitGate::zero(Wire::for_row(curr_row)));
+                        curr_row += 1;
+                    }
+
+                    gates
+                };
+
+                // witness
+                let witness = {
+                    // create row for the public value 1
+                    let mut witness: [_; COLUMNS] = array_init(|_col| vec![$F::zero(); 1]);
+                    witness[0][0] = $F::one();
+                    // create inputs to the addition
+                    let left = modulus.clone() - BigUint::from_bytes_be(&[1]);
+                    let right = modulus.clone() - BigUint::from_bytes_be(&[1]);
+                    // create a chain of 1 addition
+                    let add_witness = create_chain::<$F>(&vec![left, right], operation, modulus);
+                    for col in 0..COLUMNS {
+                        witness[col].extend(add_witness[col].iter());
+                    }
+                    // extend range checks for all of left, right, output, and bound
+                    let left = (witness[0][1], witness[1][1], witness[2][1]);
+                    range_check::witness::extend_multi(&mut witness, left.0, left.1, left.2);
+                    let right = (witness[3][1], witness[4][1], witness[5][1]);
+                    range_check::witness::extend_multi(&mut witness, right.0, right.1, right.2);
+                    let output = (witness[0][2], witness[1][2], witness[2][2]);
+                    range_check::witness::extend_multi(&mut witness, output.0, output.1, output.2);
+                    let bound = (witness[0][3], witness[1][3], witness[2][3]);
+                    range_check::witness::extend_multi(&mut witness, bound.0, bound.1, bound.2);
+                    witness
+                };
+
+                // not sure if theres a smarter way instead of the double unwrap, but should be fine in the test
+                let cs = ConstraintSystem::<$F>::create(gates)
+                    .public(num_public_inputs)
+                    .lookup(vec![range_check::gadget::lookup_table()])
+                    .build()
+                    .unwrap();
+
+                let _bases = crate::srs::releasing_runtime(|| {
+                    crate::srs::lagrange_basis(&srs.0, cs.domain.d1)
+                });
+
+                let (endo_q, _endo_r) = endos::<$OtherG>();
+                let index = ProverIndex::<$G, OpeningProof<$G>>::create(cs, endo_q, srs.0);
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+                let public_input = witness[0][0];
+                let proof = ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                    &group_map,
+                    witness,
+                    &[],
+                    &index,
+                    vec![],
+                    None,
+                )
+                .unwrap();
+                (
+                    $CamlIndex(Box::new(index)),
+                    public_input.into(),
+                    (proof, vec![public_input]).into(),
+                )
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_example_with_xor>](
+                srs: $CamlSrs,
+            ) -> (
+                $CamlIndex,
+                ($CamlF, $CamlF),
+                CamlProofWithPublic<$CamlG, $CamlF>,
+            ) {
+                use ark_ff::Zero;
+                use kimchi::circuits::{
+                    constraints::ConstraintSystem,
+                    gate::{CircuitGate, Connect},
+                    polynomial::COLUMNS,
+                    polynomials::{generic::GenericGateSpec, xor},
+                    wires::Wire,
+                };
+                use poly_commitment::srs::endos;
+
+                let num_public_inputs = 2;
+
+                // circuit
+                let gates = {
+                    // public inputs
+                    let mut gates = vec![];
+                    for row in 0..num_public_inputs {
+                        gates.push(CircuitGate::<$F>::create_generic_gadget(
+                            Wire::for_row(row),
+                            GenericGateSpec::Pub,
+                            None,
+                        ));
+                    }
+                    // 1 XOR of 128 bits. This will create 8 Xor16 gates and a Generic final gate with all zeros.
+                    CircuitGate::<$F>::extend_xor_gadget(&mut gates, 128);
+                    // connect public inputs to the inputs of the XOR
+                    gates.connect_cell_pair((0, 0), (2, 0));
+                    gates.connect_cell_pair((1, 0), (2, 1));
+
+                    // Temporary workaround for lookup-table/domain-size issue
+                    for _ in 0..(1 << 13) {
+                        gates.push(CircuitGate::zero(Wire::for_row(gates.len())));
+                    }
+                    gates
+                };
+
+                // witness
+                let witness = {
+                    let mut cols: [_; COLUMNS] = array_init(|_col| vec![$F::zero(); num_public_inputs]);
+
+                    // initialize the 2 inputs
+                    let input1 = 0xDC811727DAF22EC15927D6AA275F406Bu128;
+                    let input2 = 0xA4F4417AF072DF9016A1EAB458DA80D1u128;
+                    cols[0][0] = input1.into();
+                    cols[0][1] = input2.into();
+
+                    xor::extend_xor_witness::<$F>(&mut cols, input1.into(), input2.into(), 128);
+                    cols
+                };
+
+                // not sure if theres a smarter way instead of the double unwrap, but should be fine in the test
+                let cs = ConstraintSystem::<$F>::create(gates)
+                    .public(num_public_inputs)
+                    .lookup(vec![xor::lookup_table()])
+                    .build()
+                    .unwrap();
+
+                let _bases = crate::srs::releasing_runtime(|| {
+                    crate::srs::lagrange_basis(&srs.0, cs.domain.d1)
+                });
+
+                let (endo_q, _endo_r) = endos::<$OtherG>();
+                let index = ProverIndex::<$G, OpeningProof<$G>>::create(cs, endo_q, srs.0);
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+                let public_input = (witness[0][0], witness[0][1]);
+                let proof = ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                    &group_map,
+                    witness,
+                    &[],
+                    &index,
+                    vec![],
+                    None,
+                )
+                .unwrap();
+                (
+                    $CamlIndex(Box::new(index)),
+                    (public_input.0.into(), public_input.1.into()),
+                    (proof, vec![public_input.0, public_input.1]).into(),
+                )
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_example_with_rot>](
+                srs: $CamlSrs,
+            ) -> (
+                $CamlIndex,
+                ($CamlF, $CamlF),
+                CamlProofWithPublic<$CamlG, $CamlF>,
+            ) {
+                use ark_ff::Zero;
+                use kimchi::circuits::{
+                    constraints::ConstraintSystem,
+                    gate::{CircuitGate, Connect},
+                    polynomial::COLUMNS,
+                    polynomials::{
+                        generic::GenericGateSpec,
+                        rot::{self, RotMode},
+                    },
+                    wires::Wire,
+                };
+                use poly_commitment::srs::endos;
+
+                // Includes the actual input of the rotation and a row with the zero value
+                let num_public_inputs = 2;
+                // 1 ROT of 32 to the left
+                let rot = 32;
+                let mode = RotMode::Left;
+
+                // circuit
+                let gates = {
+                    let mut gates = vec![];
+                    // public inputs
+                    for row in 0..num_public_inputs {
+                        gates.push(CircuitGate::<$F>::create_generic_gadget(
+                            Wire::for_row(row),
+                            GenericGateSpec::Pub,
+                            None,
+                        ));
+                    }
+                    CircuitGate::<$F>::extend_rot(&mut gates, rot, mode, 1);
+                    // connect first public input to the word of the ROT
+                    gates.connect_cell_pair((0, 0), (2, 0));
+
+                    // Temporary workaround for lookup-table/domain-size issue
+                    for _ in 0..(1 << 13) {
+                        gates.push(CircuitGate::zero(Wire::for_row(gates.len())));
+                    }
+
+                    gates
+                };
+
+                // witness
+                let witness = {
+                    // create one row for the public word
+                    let mut cols: [_; COLUMNS] = array_init(|_col| vec![$F::zero(); 2]);
+
+                    // initialize the public input containing the word to be rotated
+                    let input = 0xDC811727DAF22EC1u64;
+                    cols[0][0] = input.into();
+                    rot::extend_rot::<$F>(&mut cols, input, rot, mode);
+
+                    cols
+                };
+
+                // not sure if theres a smarter way instead of the double unwrap, but should be fine in the test
+                let cs = ConstraintSystem::<$F>::create(gates)
+                    .public(num_public_inputs)
+                    .lookup(vec![rot::lookup_table()])
+                    .build()
+                    .unwrap();
+
+                let _bases = crate::srs::releasing_runtime(|| {
+                    crate::srs::lagrange_basis(&srs.0, cs.domain.d1)
+                });
+
+                let (endo_q, _endo_r) = endos::<$OtherG>();
+                let index = ProverIndex::<$G, OpeningProof<$G>>::create(cs, endo_q, srs.0);
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+                let public_input = (witness[0][0], witness[0][1]);
+                let proof = ProverProof::create_recursive::<EFqSponge, EFrSponge>(
+                    &group_map,
+                    witness,
+                    &[],
+                    &index,
+                    vec![],
+                    None,
+                )
+                .unwrap();
+                (
+                    $CamlIndex(Box::new(index)),
+                    (public_input.0.into(), public_input.1.into()),
+                    (proof, vec![public_input.0, public_input.1]).into(),
+                )
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_verify>](
+                index: $CamlVerifierIndex,
+                proof: CamlProofWithPublic<$CamlG, $CamlF>,
+            ) -> bool {
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+
+                let (proof, public_input) = proof.into();
+                let verifier_index = index.into();
+                let context = Context {
+                    verifier_index: &verifier_index,
+                    proof: &proof,
+                    public_input: &public_input,
+                };
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = verifier_lagrange_basis(&verifier_index);
+                    batch_verify::<
+                        $G,
+                        DefaultFqSponge<$CurveParams, PlonkSpongeConstantsKimchi>,
+                        DefaultFrSponge<$F, PlonkSpongeConstantsKimchi>,
+                        OpeningProof<$G>,
+                    >(&group_map, &[context])
+                    .is_ok()
+                })
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_batch_verify>](
+                indexes: Vec<$CamlVerifierIndex>,
+                proofs: Vec<CamlProofWithPublic<$CamlG, $CamlF>>,
+            ) -> bool {
+                let ts: Vec<_> = indexes
+                    .into_iter()
+                    .zip(proofs.into_iter())
+                    .map(|(caml_index, caml_proof)| {
+                        let verifier_index: VerifierIndex<$G, OpeningProof<$G>> = caml_index.into();
+                        let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<_>) =
+                            caml_proof.into();
+                        (verifier_index, proof, public_input)
+                    })
+                    .collect();
+                let ts_ref: Vec<Context<$G, OpeningProof<$G>>> = ts
+                    .iter()
+                    .map(|(verifier_index, proof, public_input)| Context {
+                        verifier_index,
+                        proof,
+                        public_input,
+                    })
+                    .collect();
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = verifier_lagrange_bases(
+                        ts.iter().map(|(verifier_index, _, _)| verifier_index),
+                    );
+                    batch_verify::<
+                        $G,
+                        DefaultFqSponge<$CurveParams, PlonkSpongeConstantsKimchi>,
+                        DefaultFrSponge<$F, PlonkSpongeConstantsKimchi>,
+                        OpeningProof<$G>,
+                    >(&group_map, &ts_ref)
+                    .is_ok()
+                })
+            }
+
+            /// Same as `verify`, with a verifier index that was converted beforehand.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_verify_with_handle>](
+                index: $CamlVerifierIndexHandle,
+                proof: CamlProofWithPublic<$CamlG, $CamlF>,
+            ) -> bool {
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+
+                let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<_>) =
+                    proof.into();
+                let context = Context {
+                    verifier_index: &index,
+                    proof: &proof,
+                    public_input: &public_input,
+                };
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = verifier_lagrange_basis(&index);
+                    batch_verify::<$G, EFqSponge, EFrSponge, OpeningProof<$G>>(
+                        &group_map,
+                        &[context],
+                    )
+                    .is_ok()
+                })
+            }
+
+            /// Verifies a batch of proofs that were all created for the same verifier index.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_batch_verify_with_handle>](
+                index: $CamlVerifierIndexHandle,
+                proofs: Vec<CamlProofWithPublic<$CamlG, $CamlF>>,
+            ) -> bool {
+                let proofs: Vec<(ProverProof<$G, OpeningProof<$G>>, Vec<_>)> =
+                    proofs.into_iter().map(Into::into).collect();
+                let contexts: Vec<Context<$G, OpeningProof<$G>>> = proofs
+                    .iter()
+                    .map(|(proof, public_input)| Context {
+                        verifier_index: &index,
+                        proof,
+                        public_input,
+                    })
+                    .collect();
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = verifier_lagrange_basis(&index);
+                    batch_verify::<$G, EFqSponge, EFrSponge, OpeningProof<$G>>(
+                        &group_map,
+                        &contexts,
+                    )
+                    .is_ok()
+                })
+            }
+
+            /// Same as `verify`, but returns why the proof does not verify.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_verify_result>](
+                index: $CamlVerifierIndex,
+                proof: CamlProofWithPublic<$CamlG, $CamlF>,
+            ) -> CamlResult<(), CamlVerifyError> {
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+
+                let (proof, public_input) = proof.into();
+                let verifier_index = index.into();
+                let context = Context {
+                    verifier_index: &verifier_index,
+                    proof: &proof,
+                    public_input: &public_input,
+                };
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = verifier_lagrange_basis(&verifier_index);
+                    verify_contexts(&group_map, &[context])
+                })
+                .into()
+            }
+
+            /// Checks that the dimensions of `proof` match `index`, without verifying it.
+            /// Proofs received from peers should go through this before `verify` or the oracles.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_check_shape>](
+                index: $CamlVerifierIndex,
+                proof: CamlProofWithPublic<$CamlG, $CamlF>,
+            ) -> CamlResult<(), CamlVerifyError> {
+                let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<$F>) =
+                    proof.into();
+                let index: VerifierIndex<$G, OpeningProof<$G>> = index.into();
+                crate::proof_shape::check_proof_shape(&index, &proof, public_input.len()).into()
+            }
+
+            /// Same as `check_shape`, with a verifier index that was converted beforehand.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_check_shape_with_handle>](
+                index: $CamlVerifierIndexHandle,
+                proof: CamlProofWithPublic<$CamlG, $CamlF>,
+            ) -> CamlResult<(), CamlVerifyError> {
+                let index: &VerifierIndex<$G, OpeningProof<$G>> = &index;
+                let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<$F>) =
+                    proof.into();
+                crate::proof_shape::check_proof_shape(index, &proof, public_input.len()).into()
+            }
+
+            /// Same as `batch_verify`, but returns the position of the first proof that
+            /// does not verify, and why.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_batch_verify_result>](
+                indexes: Vec<$CamlVerifierIndex>,
+                proofs: Vec<CamlProofWithPublic<$CamlG, $CamlF>>,
+            ) -> CamlResult<(), (ocaml::Int, CamlVerifyError)> {
+                if indexes.len() != proofs.len() {
+                    let position = std::cmp::min(indexes.len(), proofs.len());
+                    return CamlResult::Error((
+                        position as isize,
+                        CamlVerifyError::Other(format!(
+                            "got {} verifier indexes for {} proofs",
+                            indexes.len(),
+                            proofs.len()
+                        )),
+                    ));
+                }
+
+                let ts: Vec<_> = indexes
+                    .into_iter()
+                    .zip(proofs.into_iter())
+                    .map(|(caml_index, caml_proof)| {
+                        let verifier_index: VerifierIndex<$G, OpeningProof<$G>> = caml_index.into();
+                        let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<_>) =
+                            caml_proof.into();
+                        (verifier_index, proof, public_input)
+                    })
+                    .collect();
+                let ts_ref: Vec<Context<$G, OpeningProof<$G>>> = ts
+                    .iter()
+                    .map(|(verifier_index, proof, public_input)| Context {
+                        verifier_index,
+                        proof,
+                        public_input,
+                    })
+                    .collect();
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = verifier_lagrange_bases(
+                        ts.iter().map(|(verifier_index, _, _)| verifier_index),
+                    );
+                    verify_contexts_result(&group_map, &ts_ref)
+                })
+            }
+
+            /// Same as `verify_result`, with a verifier index that was converted beforehand.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_verify_result_with_handle>](
+                index: $CamlVerifierIndexHandle,
+                proof: CamlProofWithPublic<$CamlG, $CamlF>,
+            ) -> CamlResult<(), CamlVerifyError> {
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+
+                let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<_>) =
+                    proof.into();
+                let context = Context {
+                    verifier_index: &index,
+                    proof: &proof,
+                    public_input: &public_input,
+                };
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = verifier_lagrange_basis(&index);
+                    verify_contexts(&group_map, &[context])
+                })
+                .into()
+            }
+
+            /// Same as `batch_verify_with_handle`, but returns the position of the first proof
+            /// that does not verify, and why.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_batch_verify_result_with_handle>](
+                index: $CamlVerifierIndexHandle,
+                proofs: Vec<CamlProofWithPublic<$CamlG, $CamlF>>,
+            ) -> CamlResult<(), (ocaml::Int, CamlVerifyError)> {
+                let proofs: Vec<(ProverProof<$G, OpeningProof<$G>>, Vec<_>)> =
+                    proofs.into_iter().map(Into::into).collect();
+                let contexts: Vec<Context<$G, OpeningProof<$G>>> = proofs
+                    .iter()
+                    .map(|(proof, public_input)| Context {
+                        verifier_index: &index,
+                        proof,
+                        public_input,
+                    })
+                    .collect();
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+
+                crate::srs::releasing_runtime(|| {
+                    let _bases = verifier_lagrange_basis(&index);
+                    verify_contexts_result(&group_map, &contexts)
+                })
+            }
+
+            /// Verifies a batch of proofs, and returns the positions of the ones that do not verify
+            /// (in increasing order), or an empty array if they all do.
+            /// All the verifier indexes must share the same SRS.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_batch_verify_bisect>](
+                indexes: Vec<$CamlVerifierIndex>,
+                proofs: Vec<CamlProofWithPublic<$CamlG, $CamlF>>,
+            ) -> Result<Vec<ocaml::Int>, ocaml::Error> {
+                let invalid_argument = || {
+                    ocaml::Error::invalid_argument(concat!(
+                        "caml_pasta_",
+                        stringify!($name),
+                        "_plonk_proof_batch_verify_bisect"
+                    ))
+                    .err()
+                    .unwrap()
+                };
+                if indexes.len() != proofs.len() {
+                    return Err(invalid_argument());
+                }
+
+                let ts: Vec<_> = indexes
+                    .into_iter()
+                    .zip(proofs.into_iter())
+                    .map(|(caml_index, caml_proof)| {
+                        let verifier_index: VerifierIndex<$G, OpeningProof<$G>> = caml_index.into();
+                        let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<_>) =
+                            caml_proof.into();
+                        (verifier_index, proof, public_input)
+                    })
+                    .collect();
+                // the opening proofs are checked together, against a single SRS
+                let srs = match ts.first() {
+                    None => return Ok(vec![]),
+                    Some((verifier_index, _, _)) => verifier_index.srs().clone(),
+                };
+                if ts
+                    .iter()
+                    .any(|(verifier_index, _, _)| !std::sync::Arc::ptr_eq(verifier_index.srs(), &srs))
+                {
+                    return Err(invalid_argument());
+                }
+                let contexts: Vec<Context<$G, OpeningProof<$G>>> = ts
+                    .iter()
+                    .map(|(verifier_index, proof, public_input)| Context {
+                        verifier_index,
+                        proof,
+                        public_input,
+                    })
+                    .collect();
+                let group_map = <$G as CommitmentCurve>::Map::setup();
+
+                let mut invalid = crate::srs::releasing_runtime(|| {
+                    let _bases = verifier_lagrange_bases(
+                        ts.iter().map(|(verifier_index, _, _)| verifier_index),
+                    );
+                    crate::prover_pool::install(|| {
+                        // the oracles of each proof are computed once, for all the sub-batches
+                        let (valid, invalid) = batch_contexts(&contexts);
+                        let mut invalid: Vec<usize> = invalid.into_iter().map(|(i, _)| i).collect();
+                        invalid.extend(bisect_batch(&srs, &group_map, &valid));
+                        invalid
+                    })
+                });
+                invalid.sort_unstable();
+                Ok(invalid.into_iter().map(|i| i as isize).collect())
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_to_bytes>](
+                proof: CamlProofWithPublic<$CamlG, $CamlF>,
+            ) -> Result<CamlBytes, ocaml::Error> {
+                let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<$F>) =
+                    proof.into();
+                [<$name _proof_to_bytes>](&proof, &public_input)
+                    .map(CamlBytes)
+                    .map_err(ocaml::Error::Message)
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_of_bytes>](
+                bytes: CamlBytesString,
+            ) -> Result<CamlProofWithPublic<$CamlG, $CamlF>, ocaml::Error> {
+                [<$name _proof_of_bytes>](bytes.0)
+                    .map(Into::into)
+                    .map_err(ocaml::Error::Message)
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_to_json>](
+                proof: CamlProofWithPublic<$CamlG, $CamlF>,
+            ) -> Result<String, ocaml::Error> {
+                let (proof, public_input): (ProverProof<$G, OpeningProof<$G>>, Vec<$F>) =
+                    proof.into();
+                [<$name _proof_to_json>](proof, &public_input).map_err(ocaml::Error::Message)
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_of_json>](
+                json: String,
+            ) -> Result<CamlProofWithPublic<$CamlG, $CamlF>, ocaml::Error> {
+                [<$name _proof_of_json>](&json)
+                    .map(Into::into)
+                    .map_err(|e| ocaml::Error::Error(e.into()))
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_dummy>]() -> CamlProofWithPublic<$CamlG, $CamlF> {
+                fn comm() -> PolyComm<$G> {
+                    let g = $G::prime_subgroup_generator();
+                    PolyComm {
+                        shifted: Some(g),
+                        unshifted: vec![g, g, g],
+                    }
+                }
+
+                let prev = RecursionChallenge {
+                    chals: vec![$F::one(), $F::one()],
+                    comm: comm(),
+                };
+                let prev_challenges = vec![prev.clone(), prev.clone(), prev];
+
+                let g = $G::prime_subgroup_generator();
+                let proof = OpeningProof {
+                    lr: vec![(g, g), (g, g), (g, g)],
+                    z1: $F::one(),
+                    z2: $F::one(),
+                    delta: g,
+                    sg: g,
+                };
+                let eval = || PointEvaluations {
+                    zeta: vec![$F::one()],
+                    zeta_omega: vec![$F::one()],
+                };
+                let evals = ProofEvaluations {
+                    public: Some(eval()),
+                    w: array_init(|_| eval()),
+                    coefficients: array_init(|_| eval()),
+                    z: eval(),
+                    s: array_init(|_| eval()),
+                    generic_selector: eval(),
+                    poseidon_selector: eval(),
+                    complete_add_selector: eval(),
+                    mul_selector: eval(),
+                    emul_selector: eval(),
+                    endomul_scalar_selector: eval(),
+                    range_check0_selector: None,
+                    range_check1_selector: None,
+                    foreign_field_add_selector: None,
+                    foreign_field_mul_selector: None,
+                    xor_selector: None,
+                    rot_selector: None,
+                    lookup_aggregation: None,
+                    lookup_table: None,
+                    lookup_sorted: array::from_fn(|_| None),
+                    runtime_lookup_table: None,
+                    runtime_lookup_table_selector: None,
+                    xor_lookup_selector: None,
+                    lookup_gate_lookup_selector: None,
+                    range_check_lookup_selector: None,
+                    foreign_field_mul_lookup_selector: None,
+                };
+
+                let public = vec![$F::one(), $F::one()];
+                let dlogproof = ProverProof {
+                    commitments: ProverCommitments {
+                        w_comm: array_init(|_| comm()),
+                        z_comm: comm(),
+                        t_comm: comm(),
+                        lookup: None,
+                    },
+                    proof,
+                    evals,
+                    ft_eval1: $F::one(),
+                    prev_challenges,
+                };
+
+                (dlogproof, public).into()
+            }
+
+            /// Same as `dummy`, with the dimensions of a circuit of the given shape.
+            /// `prev_challenge_rounds` is the number of rounds of the opening proofs of the
+            /// other curve, i.e. the base 2 logarithm of the size of its SRS.
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_dummy_with_shape>](
+                shape: crate::dummy::CamlDummyShape,
+                prev_challenge_rounds: ocaml::Int,
+            ) -> Result<CamlProofWithPublic<$CamlG, $CamlF>, ocaml::Error> {
+                let shape: crate::dummy::DummyShape = shape.try_into()?;
+                let prev_challenge_rounds = usize::try_from(prev_challenge_rounds).map_err(|_| {
+                    ocaml::Error::invalid_argument("prev_challenge_rounds")
+                        .err()
+                        .unwrap()
+                })?;
+                Ok(shape.proof::<$G>(prev_challenge_rounds).into())
+            }
+
+            #[ocaml_gen::func]
+            #[ocaml::func]
+            pub fn [<caml_pasta_ $name _plonk_proof_deep_copy>](
+                x: CamlProofWithPublic<$CamlG, $CamlF>,
+            ) -> CamlProofWithPublic<$CamlG, $CamlF> {
+                x
+            }
+
+            #[cfg(test)]
+            mod tests {
+                use super::*;
+                use crate::dummy::DummyShape;
+                use kimchi::circuits::{
+                    constraints::FeatureFlags,
+                    lookup::lookups::{LookupFeatures, LookupPatterns},
+                };
+
+                fn encoded_proof() -> Vec<u8> {
+                    let lookup_features = LookupFeatures {
+                        patterns: LookupPatterns {
+                            xor: false,
+                            lookup: true,
+                            range_check: true,
+                            foreign_field_mul: false,
+                        },
+                        joint_lookup_used: true,
+                        uses_runtime_tables: false,
+                    };
+                    let shape = DummyShape::for_tests(FeatureFlags {
+                        range_check0: true,
+                        range_check1: true,
+                        foreign_field_add: false,
+                        foreign_field_mul: false,
+                        xor: false,
+                        rot: true,
+                        lookup_features,
+                    });
+                    let (proof, public_input) = shape.proof::<$G>(15);
+                    [<$name _proof_to_bytes>](&proof, &public_input).unwrap()
+                }
+
+                #[test]
+                fn proof_bytes_round_trip() {
+                    let bytes = encoded_proof();
+                    let (proof, public_input) = [<$name _proof_of_bytes>](&bytes).unwrap();
+                    assert_eq!(public_input.len(), 3);
+                    assert_eq!([<$name _proof_to_bytes>](&proof, &public_input).unwrap(), bytes);
+                }
+
+                #[test]
+                fn proof_bytes_reject_truncated_input() {
+                    let bytes = encoded_proof();
+                    for len in [0, 4, bytes.len() / 2, bytes.len() - 1] {
+                        assert!([<$name _proof_of_bytes>](&bytes[..len]).is_err());
+                    }
+                }
+
+                #[test]
+                fn proof_bytes_reject_trailing_bytes() {
+                    let mut bytes = encoded_proof();
+                    bytes.push(0);
+                    assert_eq!(
+                        [<$name _proof_of_bytes>](&bytes).err(),
+                        Some("trailing bytes after the proof")
+                    );
+                }
+
+                #[test]
+                fn proof_bytes_reject_a_wrong_version() {
+                    let mut bytes = encoded_proof();
+                    bytes[crate::plonk_proof::PROOF_ENCODING_MAGIC.len()] += 1;
+                    assert_eq!(
+                        [<$name _proof_of_bytes>](&bytes).err(),
+                        Some("unsupported encoding version")
+                    );
+                }
+
+                #[test]
+                fn proof_json_round_trip() {
+                    let bytes = encoded_proof();
+                    let (proof, public_input) = [<$name _proof_of_bytes>](&bytes).unwrap();
+                    let json = [<$name _proof_to_json>](proof, &public_input).unwrap();
+                    let (proof, public_input) = [<$name _proof_of_json>](&json).unwrap();
+                    assert_eq!([<$name _proof_to_bytes>](&proof, &public_input).unwrap(), bytes);
+                }
+
+                #[test]
+                fn proof_json_errors_say_what_is_wrong() {
+                    let err = [<$name _proof_of_json>]("{\"public_input\": []}").unwrap_err();
+                    assert!(err.starts_with("could not parse the proof: missing field `proof`"));
+                }
+            }
+        }
+    };
+}
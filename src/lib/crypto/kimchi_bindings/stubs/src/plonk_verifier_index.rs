@@ -1,8 +1,21 @@
+use crate::encoding::{decode_header, encode_header, Reader, Writer};
 use ark_ec::AffineCurve;
+use ark_ff::{PrimeField, SquareRootField};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use kimchi::circuits::constraints::FeatureFlags;
 use kimchi::circuits::lookup::index::LookupSelectors;
-use kimchi::circuits::lookup::lookups::{LookupFeatures, LookupInfo};
-use kimchi::verifier_index::LookupVerifierIndex;
-use poly_commitment::{commitment::CommitmentCurve, PolyComm};
+use kimchi::circuits::lookup::lookups::{LookupFeatures, LookupInfo, LookupPatterns};
+use kimchi::circuits::polynomials::permutation::{permutation_vanishing_polynomial, zk_w};
+use kimchi::circuits::wires::{COLUMNS, PERMUTS};
+use kimchi::curve::KimchiCurve;
+use kimchi::linearization::expr_linearization;
+use kimchi::verifier_index::{LookupVerifierIndex, VerifierIndex};
+use once_cell::sync::OnceCell;
+use poly_commitment::{
+    commitment::CommitmentCurve, evaluation_proof::OpeningProof, srs::SRS, PolyComm,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
 pub struct CamlPlonkDomain<Fr> {
@@ -196,3 +209,493 @@ pub struct CamlPlonkVerifierIndex<Fr, SRS, PolyComm> {
     pub lookup_index: Option<CamlLookupVerifierIndex<PolyComm>>,
     pub zk_rows: ocaml::Int,
 }
+
+/// The first bytes of a verifier index encoded by `*_plonk_verifier_index_to_bytes`.
+pub const VERIFIER_INDEX_ENCODING_MAGIC: &[u8; 4] = b"KVIX";
+
+/// The version of the encoding of verifier indexes, to bump whenever the encoding changes.
+pub const VERIFIER_INDEX_ENCODING_VERSION: u8 = 1;
+
+/// The custom gates and lookups that a verifier index enables.
+pub fn verifier_index_feature_flags<G: KimchiCurve>(
+    index: &VerifierIndex<G, OpeningProof<G>>,
+) -> FeatureFlags {
+    FeatureFlags {
+        range_check0: index.range_check0_comm.is_some(),
+        range_check1: index.range_check1_comm.is_some(),
+        foreign_field_add: index.foreign_field_add_comm.is_some(),
+        foreign_field_mul: index.foreign_field_mul_comm.is_some(),
+        rot: index.rot_comm.is_some(),
+        xor: index.xor_comm.is_some(),
+        lookup_features: match &index.lookup_index {
+            Some(lookup) => lookup.lookup_info.features,
+            None => LookupFeatures {
+                patterns: LookupPatterns {
+                    xor: false,
+                    lookup: false,
+                    range_check: false,
+                    foreign_field_mul: false,
+                },
+                joint_lookup_used: false,
+                uses_runtime_tables: false,
+            },
+        },
+    }
+}
+
+/// Encodes a verifier index, without its SRS, as the header of [crate::encoding]
+/// (with [VERIFIER_INDEX_ENCODING_MAGIC] and `tag`) followed by, in order:
+///
+/// * the base-2 logarithm of the size of the domain, as one byte,
+/// * `max_poly_size`, `zk_rows`, `public` and `prev_challenges`, as 8 bytes each,
+/// * the `PERMUTS` sigma and `COLUMNS` coefficients commitments, then the generic,
+///   permutation, complete add, mul, emul and endomul scalar selector commitments,
+/// * the xor, range check 0 and 1, foreign field add and mul, and rot selector commitments,
+///   each after a presence byte,
+/// * the `PERMUTS` shifts,
+/// * a presence byte, then the lookup index if present: whether joint lookups are used,
+///   the number of lookup table columns and their commitments, the xor, lookup,
+///   range check and foreign field mul selectors and the table IDs (each after a presence byte),
+///   `max_per_row` (8 bytes), `max_joint_size` (4 bytes), the lookup features as six bytes
+///   (the four patterns in the same order, joint lookups, runtime tables),
+///   and the optional runtime tables selector.
+///
+/// A commitment is its number of chunks (4 bytes), its compressed chunks, and its optional
+/// compressed shifted part after a presence byte. The fields that kimchi derives from these
+/// (the linearization, the powers of alpha, the vanishing polynomial, `w` and `endo`) are skipped.
+pub fn verifier_index_to_bytes<G: KimchiCurve>(
+    index: &VerifierIndex<G, OpeningProof<G>>,
+    tag: &str,
+) -> Result<Vec<u8>, &'static str> {
+    let mut w = Writer(vec![]);
+    encode_header(
+        &mut w.0,
+        VERIFIER_INDEX_ENCODING_MAGIC,
+        VERIFIER_INDEX_ENCODING_VERSION,
+        tag,
+    );
+
+    let log2_size = u8::try_from(index.domain.log_size_of_group)
+        .map_err(|_| "the domain of the verifier index is too large")?;
+    w.u8(log2_size);
+    w.u64(index.max_poly_size as u64);
+    w.u64(index.zk_rows);
+    w.u64(index.public as u64);
+    w.u64(index.prev_challenges as u64);
+
+    for comm in index.sigma_comm.iter().chain(&index.coefficients_comm) {
+        write_comm(&mut w, comm)?;
+    }
+    for comm in [
+        &index.generic_comm,
+        &index.psm_comm,
+        &index.complete_add_comm,
+        &index.mul_comm,
+        &index.emul_comm,
+        &index.endomul_scalar_comm,
+    ] {
+        write_comm(&mut w, comm)?;
+    }
+    for comm in [
+        &index.xor_comm,
+        &index.range_check0_comm,
+        &index.range_check1_comm,
+        &index.foreign_field_add_comm,
+        &index.foreign_field_mul_comm,
+        &index.rot_comm,
+    ] {
+        write_optional_comm(&mut w, comm.as_ref())?;
+    }
+    for shift in &index.shift {
+        w.canonical(shift)?;
+    }
+
+    w.bool(index.lookup_index.is_some());
+    if let Some(lookup) = &index.lookup_index {
+        w.bool(lookup.joint_lookup_used);
+        w.length(lookup.lookup_table.len())?;
+        for comm in &lookup.lookup_table {
+            write_comm(&mut w, comm)?;
+        }
+        let selectors = &lookup.lookup_selectors;
+        for comm in [
+            &selectors.xor,
+            &selectors.lookup,
+            &selectors.range_check,
+            &selectors.ffmul,
+            &lookup.table_ids,
+        ] {
+            write_optional_comm(&mut w, comm.as_ref())?;
+        }
+        let info = &lookup.lookup_info;
+        w.u64(info.max_per_row as u64);
+        w.u32(info.max_joint_size);
+        let features = &info.features;
+        for flag in [
+            features.patterns.xor,
+            features.patterns.lookup,
+            features.patterns.range_check,
+            features.patterns.foreign_field_mul,
+            features.joint_lookup_used,
+            features.uses_runtime_tables,
+        ] {
+            w.bool(flag);
+        }
+        write_optional_comm(&mut w, lookup.runtime_tables_selector.as_ref())?;
+    }
+
+    Ok(w.0)
+}
+
+fn write_comm<G: KimchiCurve>(w: &mut Writer, comm: &PolyComm<G>) -> Result<(), &'static str> {
+    w.length(comm.unshifted.len())?;
+    for chunk in &comm.unshifted {
+        w.canonical(chunk)?;
+    }
+    w.bool(comm.shifted.is_some());
+    if let Some(shifted) = &comm.shifted {
+        w.canonical(shifted)?;
+    }
+    Ok(())
+}
+
+fn write_optional_comm<G: KimchiCurve>(
+    w: &mut Writer,
+    comm: Option<&PolyComm<G>>,
+) -> Result<(), &'static str> {
+    w.bool(comm.is_some());
+    if let Some(comm) = comm {
+        write_comm(w, comm)?;
+    }
+    Ok(())
+}
+
+fn read_comm<G: KimchiCurve>(r: &mut Reader) -> Result<PolyComm<G>, &'static str> {
+    let chunks = r.length()?;
+    // every chunk takes at least one byte, which bounds the allocation
+    if chunks > r.0.len() {
+        return Err("unexpected end of the bytes");
+    }
+    let unshifted = (0..chunks)
+        .map(|_| r.canonical())
+        .collect::<Result<_, _>>()?;
+    let shifted = if r.bool()? {
+        Some(r.canonical()?)
+    } else {
+        None
+    };
+    Ok(PolyComm { unshifted, shifted })
+}
+
+fn read_optional_comm<G: KimchiCurve>(r: &mut Reader) -> Result<Option<PolyComm<G>>, &'static str> {
+    if r.bool()? {
+        Ok(Some(read_comm(r)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Attaches `srs` to a verifier index decoded without it, and recomputes the fields that
+/// the encodings skip: the linearization, the powers of alpha, the vanishing polynomial,
+/// `w` and `endo`.
+fn with_derived_fields<G: KimchiCurve>(
+    mut index: VerifierIndex<G, OpeningProof<G>>,
+    srs: Arc<SRS<G>>,
+) -> VerifierIndex<G, OpeningProof<G>>
+where
+    G::ScalarField: PrimeField + SquareRootField,
+{
+    index.srs = srs;
+    index.permutation_vanishing_polynomial_m = OnceCell::with_value(
+        permutation_vanishing_polynomial(index.domain, index.zk_rows),
+    );
+    index.w = OnceCell::with_value(zk_w(index.domain, index.zk_rows));
+    index.endo = *G::other_curve_endo();
+    let (linearization, powers_of_alpha) =
+        expr_linearization(Some(&verifier_index_feature_flags(&index)), true);
+    index.linearization = linearization;
+    index.powers_of_alpha = powers_of_alpha;
+    index
+}
+
+/// Decodes a verifier index encoded by [verifier_index_to_bytes], attaches `srs` to it,
+/// and recomputes the fields that the encoding skips.
+pub fn verifier_index_of_bytes<G: KimchiCurve>(
+    bytes: &[u8],
+    tag: &str,
+    srs: Arc<SRS<G>>,
+) -> Result<VerifierIndex<G, OpeningProof<G>>, &'static str>
+where
+    G::ScalarField: PrimeField + SquareRootField,
+{
+    let mut r = Reader(decode_header(
+        bytes,
+        VERIFIER_INDEX_ENCODING_MAGIC,
+        VERIFIER_INDEX_ENCODING_VERSION,
+        tag,
+    )?);
+
+    let domain = Radix2EvaluationDomain::<G::ScalarField>::new(1 << r.u8()?.min(63))
+        .ok_or("the domain of the verifier index is too large")?;
+    let max_poly_size = r.u64()? as usize;
+    let zk_rows = r.u64()?;
+    let public = r.u64()? as usize;
+    let prev_challenges = r.u64()? as usize;
+
+    let sigma_comm: [PolyComm<G>; PERMUTS] = array_init::try_array_init(|_| read_comm(&mut r))?;
+    let coefficients_comm: [PolyComm<G>; COLUMNS] =
+        array_init::try_array_init(|_| read_comm(&mut r))?;
+    let generic_comm = read_comm(&mut r)?;
+    let psm_comm = read_comm(&mut r)?;
+    let complete_add_comm = read_comm(&mut r)?;
+    let mul_comm = read_comm(&mut r)?;
+    let emul_comm = read_comm(&mut r)?;
+    let endomul_scalar_comm = read_comm(&mut r)?;
+    let xor_comm = read_optional_comm(&mut r)?;
+    let range_check0_comm = read_optional_comm(&mut r)?;
+    let range_check1_comm = read_optional_comm(&mut r)?;
+    let foreign_field_add_comm = read_optional_comm(&mut r)?;
+    let foreign_field_mul_comm = read_optional_comm(&mut r)?;
+    let rot_comm = read_optional_comm(&mut r)?;
+    let shift: [G::ScalarField; PERMUTS] = array_init::try_array_init(|_| r.canonical())?;
+
+    let lookup_index = if r.bool()? {
+        let joint_lookup_used = r.bool()?;
+        let columns = r.length()?;
+        if columns > r.0.len() {
+            return Err("unexpected end of the bytes");
+        }
+        let lookup_table = (0..columns)
+            .map(|_| read_comm(&mut r))
+            .collect::<Result<_, _>>()?;
+        let lookup_selectors = LookupSelectors {
+            xor: read_optional_comm(&mut r)?,
+            lookup: read_optional_comm(&mut r)?,
+            range_check: read_optional_comm(&mut r)?,
+            ffmul: read_optional_comm(&mut r)?,
+        };
+        let table_ids = read_optional_comm(&mut r)?;
+        let max_per_row = r.u64()? as usize;
+        let max_joint_size = r.u32()?;
+        let features = LookupFeatures {
+            patterns: LookupPatterns {
+                xor: r.bool()?,
+                lookup: r.bool()?,
+                range_check: r.bool()?,
+                foreign_field_mul: r.bool()?,
+            },
+            joint_lookup_used: r.bool()?,
+            uses_runtime_tables: r.bool()?,
+        };
+        Some(LookupVerifierIndex {
+            joint_lookup_used,
+            lookup_table,
+            lookup_selectors,
+            table_ids,
+            lookup_info: LookupInfo {
+                features,
+                max_per_row,
+                max_joint_size,
+            },
+            runtime_tables_selector: read_optional_comm(&mut r)?,
+        })
+    } else {
+        None
+    };
+    r.finish()?;
+
+    let index = VerifierIndex {
+        domain,
+        max_poly_size,
+        public,
+        prev_challenges,
+        // the derived fields are set by `with_derived_fields`
+        powers_of_alpha: Default::default(),
+        srs: srs.clone(),
+        zk_rows,
+        sigma_comm,
+        coefficients_comm,
+        generic_comm,
+        psm_comm,
+        complete_add_comm,
+        mul_comm,
+        emul_comm,
+        endomul_scalar_comm,
+        xor_comm,
+        range_check0_comm,
+        range_check1_comm,
+        foreign_field_add_comm,
+        foreign_field_mul_comm,
+        rot_comm,
+        shift,
+        permutation_vanishing_polynomial_m: OnceCell::new(),
+        w: OnceCell::new(),
+        endo: Default::default(),
+        lookup_index,
+        linearization: Default::default(),
+    };
+    let index = with_derived_fields(index, srs);
+
+    // arkworks ignores the coordinates of the points at infinity
+    if verifier_index_to_bytes(&index, tag)? != bytes {
+        return Err("the verifier index is not canonically encoded");
+    }
+    Ok(index)
+}
+
+/// The JSON form of a verifier index, without its SRS. The feature flags are
+/// implied by the index, and only spelled out for readers.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "VerifierIndex<G, OpeningProof<G>>: Serialize",
+    deserialize = "VerifierIndex<G, OpeningProof<G>>: Deserialize<'de>"
+))]
+pub struct VerifierIndexJson<G: KimchiCurve> {
+    pub feature_flags: FeatureFlags,
+    pub index: VerifierIndex<G, OpeningProof<G>>,
+}
+
+pub fn verifier_index_to_json<G: KimchiCurve>(
+    index: VerifierIndex<G, OpeningProof<G>>,
+) -> Result<String, &'static str>
+where
+    VerifierIndex<G, OpeningProof<G>>: Serialize,
+{
+    let json = VerifierIndexJson {
+        feature_flags: verifier_index_feature_flags(&index),
+        index,
+    };
+    serde_json::to_string(&json).map_err(|_| "could not serialize the verifier index")
+}
+
+/// Parses a verifier index from [verifier_index_to_json], attaches `srs` to it,
+/// and recomputes the fields that the JSON skips.
+pub fn verifier_index_of_json<G: KimchiCurve>(
+    json: &str,
+    srs: Arc<SRS<G>>,
+) -> Result<VerifierIndex<G, OpeningProof<G>>, &'static str>
+where
+    VerifierIndex<G, OpeningProof<G>>: DeserializeOwned,
+    G::ScalarField: PrimeField + SquareRootField,
+{
+    let json: VerifierIndexJson<G> =
+        serde_json::from_str(json).map_err(|_| "could not parse the verifier index")?;
+    if verifier_index_feature_flags(&json.index) != json.feature_flags {
+        return Err("the feature flags do not match the verifier index");
+    }
+    Ok(with_derived_fields(json.index, srs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dummy::DummyShape;
+    use crate::pasta_fp_plonk_verifier_index::CamlPastaFpPlonkVerifierIndex;
+    use crate::srs::fp::CamlFpSrs;
+    use mina_curves::pasta::Vesta;
+
+    fn verifier_index() -> VerifierIndex<Vesta, OpeningProof<Vesta>> {
+        let lookup_features = LookupFeatures {
+            patterns: LookupPatterns {
+                xor: true,
+                lookup: true,
+                range_check: false,
+                foreign_field_mul: false,
+            },
+            joint_lookup_used: true,
+            uses_runtime_tables: true,
+        };
+        let shape = DummyShape {
+            num_chunks: 2,
+            ..DummyShape::for_tests(FeatureFlags {
+                range_check0: false,
+                range_check1: false,
+                foreign_field_add: true,
+                foreign_field_mul: false,
+                xor: true,
+                rot: true,
+                lookup_features,
+            })
+        };
+        let srs = CamlFpSrs::new(SRS::create(shape.max_poly_size()));
+        let index: CamlPastaFpPlonkVerifierIndex = shape.verifier_index::<Vesta, _, _, _>(srs);
+        index.into()
+    }
+
+    #[test]
+    fn verifier_index_bytes_round_trip() {
+        let index = verifier_index();
+        let bytes = verifier_index_to_bytes(&index, "fp").unwrap();
+        let decoded = verifier_index_of_bytes(&bytes, "fp", index.srs.clone()).unwrap();
+
+        assert_eq!(decoded.domain, index.domain);
+        assert_eq!(decoded.sigma_comm, index.sigma_comm);
+        assert_eq!(decoded.shift, index.shift);
+        assert_eq!(
+            verifier_index_feature_flags(&decoded),
+            verifier_index_feature_flags(&index)
+        );
+        assert_eq!(decoded.endo, index.endo);
+        assert_eq!(verifier_index_to_bytes(&decoded, "fp").unwrap(), bytes);
+    }
+
+    #[test]
+    fn verifier_index_json_round_trip() {
+        let index = verifier_index();
+        let json = verifier_index_to_json(index.clone()).unwrap();
+        let decoded = verifier_index_of_json(&json, index.srs.clone()).unwrap();
+
+        assert_eq!(decoded.endo, index.endo);
+        assert_eq!(decoded.w.get(), Some(index.w()));
+        assert_eq!(
+            decoded.linearization.index_terms.len(),
+            index.linearization.index_terms.len()
+        );
+        assert_eq!(
+            verifier_index_to_bytes(&decoded, "fp").unwrap(),
+            verifier_index_to_bytes(&index, "fp").unwrap()
+        );
+    }
+
+    #[test]
+    fn verifier_index_bytes_reject_a_bad_header() {
+        let index = verifier_index();
+        let mut bytes = verifier_index_to_bytes(&index, "fp").unwrap();
+        assert_eq!(
+            verifier_index_of_bytes(&bytes, "fq", index.srs.clone()).err(),
+            Some("encoded for the other curve")
+        );
+        bytes[0] ^= 1;
+        assert_eq!(
+            verifier_index_of_bytes(&bytes, "fp", index.srs.clone()).err(),
+            Some("unexpected magic bytes")
+        );
+    }
+
+    #[test]
+    fn verifier_index_bytes_reject_a_bad_version() {
+        let index = verifier_index();
+        let mut bytes = verifier_index_to_bytes(&index, "fp").unwrap();
+        bytes[VERIFIER_INDEX_ENCODING_MAGIC.len()] = VERIFIER_INDEX_ENCODING_VERSION + 1;
+        assert_eq!(
+            verifier_index_of_bytes(&bytes, "fp", index.srs.clone()).err(),
+            Some("unsupported encoding version")
+        );
+    }
+
+    #[test]
+    fn verifier_index_bytes_reject_trailing_and_missing_bytes() {
+        let index = verifier_index();
+        let mut bytes = verifier_index_to_bytes(&index, "fp").unwrap();
+        bytes.push(0);
+        assert_eq!(
+            verifier_index_of_bytes(&bytes, "fp", index.srs.clone()).err(),
+            Some("trailing bytes")
+        );
+        bytes.truncate(bytes.len() - 2);
+        assert_eq!(
+            verifier_index_of_bytes(&bytes, "fp", index.srs.clone()).err(),
+            Some("unexpected end of the bytes")
+        );
+    }
+}
@@ -0,0 +1,301 @@
+//! Checks that the dimensions of a proof match its verifier index, so that
+//! proofs received from peers can be rejected before the verifier indexes
+//! into them.
+
+use crate::verify_error::{check_public_input_length, CamlVerifyError};
+use ark_poly::EvaluationDomain;
+use kimchi::{
+    curve::KimchiCurve,
+    proof::{PointEvaluations, ProverProof},
+    verifier_index::VerifierIndex,
+};
+use poly_commitment::{commitment::PolyComm, evaluation_proof::OpeningProof};
+
+/// The number of pieces of the quotient polynomial, per chunk.
+pub const QUOTIENT_PIECES: usize = 7;
+
+fn malformed(message: String) -> CamlVerifyError {
+    CamlVerifyError::IncorrectCommitmentShape(message)
+}
+
+fn mismatch(message: String) -> CamlVerifyError {
+    CamlVerifyError::VerifierIndexMismatch(message)
+}
+
+fn check_comm<G>(name: &str, comm: &PolyComm<G>, chunks: usize) -> Result<(), CamlVerifyError> {
+    if comm.unshifted.len() != chunks {
+        return Err(malformed(format!(
+            "the commitment {name} has {} chunks, but the verifier index expects {chunks}",
+            comm.unshifted.len()
+        )));
+    }
+    Ok(())
+}
+
+fn check_evals<F>(
+    name: &str,
+    evals: &PointEvaluations<Vec<F>>,
+    chunks: usize,
+) -> Result<(), CamlVerifyError> {
+    for (point, evals) in [("zeta", &evals.zeta), ("zeta_omega", &evals.zeta_omega)] {
+        if evals.len() != chunks {
+            return Err(malformed(format!(
+                "the evaluation of {name} at {point} has {} chunks, but the verifier index expects {chunks}",
+                evals.len()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks an evaluation that the proof must have if and only if `expected`.
+fn check_optional_evals<F>(
+    name: &str,
+    evals: &Option<PointEvaluations<Vec<F>>>,
+    expected: bool,
+    chunks: usize,
+) -> Result<(), CamlVerifyError> {
+    match (evals, expected) {
+        (Some(evals), true) => check_evals(name, evals, chunks),
+        (None, false) => Ok(()),
+        (None, true) => Err(mismatch(format!(
+            "the proof has no evaluation of {name}, which the verifier index uses"
+        ))),
+        (Some(_), false) => Err(mismatch(format!(
+            "the proof has an evaluation of {name}, which the verifier index does not use"
+        ))),
+    }
+}
+
+/// Checks every dimension of `proof`, and the length of its public input,
+/// against what `index` expects.
+pub fn check_proof_shape<G: KimchiCurve>(
+    index: &VerifierIndex<G, OpeningProof<G>>,
+    proof: &ProverProof<G, OpeningProof<G>>,
+    public_input_length: usize,
+) -> Result<(), CamlVerifyError> {
+    check_public_input_length(index.public, public_input_length)?;
+
+    if index.max_poly_size == 0 {
+        return Err(mismatch(
+            "the verifier index has a max_poly_size of 0".to_string(),
+        ));
+    }
+    // same as in kimchi's verifier
+    let chunks = std::cmp::max(1, index.domain.size() / index.max_poly_size);
+
+    // commitments
+    let commitments = &proof.commitments;
+    for (i, comm) in commitments.w_comm.iter().enumerate() {
+        check_comm(&format!("w_comm.({i})"), comm, chunks)?;
+    }
+    check_comm("z_comm", &commitments.z_comm, chunks)?;
+    check_comm("t_comm", &commitments.t_comm, QUOTIENT_PIECES * chunks)?;
+
+    let lookup_info = index
+        .lookup_index
+        .as_ref()
+        .map(|lookup| &lookup.lookup_info);
+    let uses_runtime_tables = matches!(
+        lookup_info,
+        Some(info) if info.features.uses_runtime_tables
+    );
+    let num_sorted = lookup_info.map(|info| info.max_per_row + 1).unwrap_or(0);
+    match (&commitments.lookup, lookup_info) {
+        (None, None) => (),
+        (None, Some(_)) => {
+            return Err(mismatch(
+                "the proof has no lookup commitments, but the verifier index uses lookups"
+                    .to_string(),
+            ))
+        }
+        (Some(_), None) => {
+            return Err(mismatch(
+                "the proof has lookup commitments, but the verifier index does not use lookups"
+                    .to_string(),
+            ))
+        }
+        (Some(lookup), Some(_)) => {
+            if lookup.sorted.len() != num_sorted {
+                return Err(mismatch(format!(
+                    "the proof has {} sorted lookup commitments, but the verifier index expects {num_sorted}",
+                    lookup.sorted.len()
+                )));
+            }
+            for (i, comm) in lookup.sorted.iter().enumerate() {
+                check_comm(&format!("lookup.sorted.({i})"), comm, chunks)?;
+            }
+            check_comm("lookup.aggreg", &lookup.aggreg, chunks)?;
+            match (&lookup.runtime, uses_runtime_tables) {
+                (Some(comm), true) => check_comm("lookup.runtime", comm, chunks)?,
+                (None, false) => (),
+                (None, true) => {
+                    return Err(mismatch(
+                        "the proof has no runtime table commitment, but the verifier index uses runtime tables"
+                            .to_string(),
+                    ))
+                }
+                (Some(_), false) => {
+                    return Err(mismatch(
+                        "the proof has a runtime table commitment, but the verifier index does not use runtime tables"
+                            .to_string(),
+                    ))
+                }
+            }
+        }
+    }
+
+    // evaluations
+    let evals = &proof.evals;
+    if let Some(public) = &evals.public {
+        check_evals("public", public, chunks)?;
+    }
+    for (i, e) in evals.w.iter().enumerate() {
+        check_evals(&format!("w.({i})"), e, chunks)?;
+    }
+    check_evals("z", &evals.z, chunks)?;
+    for (i, e) in evals.s.iter().enumerate() {
+        check_evals(&format!("s.({i})"), e, chunks)?;
+    }
+    for (i, e) in evals.coefficients.iter().enumerate() {
+        check_evals(&format!("coefficients.({i})"), e, chunks)?;
+    }
+    check_evals("generic_selector", &evals.generic_selector, chunks)?;
+    check_evals("poseidon_selector", &evals.poseidon_selector, chunks)?;
+    check_evals(
+        "complete_add_selector",
+        &evals.complete_add_selector,
+        chunks,
+    )?;
+    check_evals("mul_selector", &evals.mul_selector, chunks)?;
+    check_evals("emul_selector", &evals.emul_selector, chunks)?;
+    check_evals(
+        "endomul_scalar_selector",
+        &evals.endomul_scalar_selector,
+        chunks,
+    )?;
+
+    let selectors = index
+        .lookup_index
+        .as_ref()
+        .map(|lookup| &lookup.lookup_selectors);
+    let optional_evals = [
+        (
+            "range_check0_selector",
+            &evals.range_check0_selector,
+            index.range_check0_comm.is_some(),
+        ),
+        (
+            "range_check1_selector",
+            &evals.range_check1_selector,
+            index.range_check1_comm.is_some(),
+        ),
+        (
+            "foreign_field_add_selector",
+            &evals.foreign_field_add_selector,
+            index.foreign_field_add_comm.is_some(),
+        ),
+        (
+            "foreign_field_mul_selector",
+            &evals.foreign_field_mul_selector,
+            index.foreign_field_mul_comm.is_some(),
+        ),
+        (
+            "xor_selector",
+            &evals.xor_selector,
+            index.xor_comm.is_some(),
+        ),
+        (
+            "rot_selector",
+            &evals.rot_selector,
+            index.rot_comm.is_some(),
+        ),
+        (
+            "lookup_aggregation",
+            &evals.lookup_aggregation,
+            lookup_info.is_some(),
+        ),
+        ("lookup_table", &evals.lookup_table, lookup_info.is_some()),
+        (
+            "runtime_lookup_table",
+            &evals.runtime_lookup_table,
+            uses_runtime_tables,
+        ),
+        (
+            "runtime_lookup_table_selector",
+            &evals.runtime_lookup_table_selector,
+            index
+                .lookup_index
+                .as_ref()
+                .map_or(false, |lookup| lookup.runtime_tables_selector.is_some()),
+        ),
+        (
+            "xor_lookup_selector",
+            &evals.xor_lookup_selector,
+            selectors.map_or(false, |s| s.xor.is_some()),
+        ),
+        (
+            "lookup_gate_lookup_selector",
+            &evals.lookup_gate_lookup_selector,
+            selectors.map_or(false, |s| s.lookup.is_some()),
+        ),
+        (
+            "range_check_lookup_selector",
+            &evals.range_check_lookup_selector,
+            selectors.map_or(false, |s| s.range_check.is_some()),
+        ),
+        (
+            "foreign_field_mul_lookup_selector",
+            &evals.foreign_field_mul_lookup_selector,
+            selectors.map_or(false, |s| s.ffmul.is_some()),
+        ),
+    ];
+    for (name, e, expected) in optional_evals {
+        check_optional_evals(name, e, expected, chunks)?;
+    }
+    if num_sorted > evals.lookup_sorted.len() {
+        return Err(mismatch(format!(
+            "the verifier index expects {num_sorted} sorted lookup evaluations, but proofs have at most {}",
+            evals.lookup_sorted.len()
+        )));
+    }
+    for (i, e) in evals.lookup_sorted.iter().enumerate() {
+        check_optional_evals(&format!("lookup_sorted.({i})"), e, i < num_sorted, chunks)?;
+    }
+
+    // opening proof, whose rounds halve the SRS down to a single point
+    let rounds = index.srs().g.len().next_power_of_two().trailing_zeros() as usize;
+    if proof.proof.lr.len() != rounds {
+        return Err(malformed(format!(
+            "the opening proof has {} rounds, but the SRS of the verifier index needs {rounds}",
+            proof.proof.lr.len()
+        )));
+    }
+
+    // previous challenges, whose number of rounds depends on the SRS of the other curve
+    if proof.prev_challenges.len() != index.prev_challenges {
+        return Err(mismatch(format!(
+            "the proof has {} previous challenges, but the verifier index expects {}",
+            proof.prev_challenges.len(),
+            index.prev_challenges
+        )));
+    }
+    if let Some(first) = proof.prev_challenges.first() {
+        for (i, prev) in proof.prev_challenges.iter().enumerate() {
+            if prev.chals.is_empty() || prev.chals.len() != first.chals.len() {
+                return Err(malformed(format!(
+                    "the previous challenges {i} have {} rounds, but the first ones have {}",
+                    prev.chals.len(),
+                    first.chals.len()
+                )));
+            }
+            if prev.comm.unshifted.is_empty() {
+                return Err(malformed(format!(
+                    "the commitment of the previous challenges {i} is empty"
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,238 @@
+//! The thread pools used to create indexes, proofs and accumulators.
+//!
+//! Kimchi and arkworks parallelize with rayon, so running them inside a
+//! pool bounds the threads they use, instead of competing for rayon's global
+//! pool with the rest of the process.
+//!
+//! Pools are named, and configured from OCaml. Each OCaml thread selects the pool
+//! that its work runs in, so that e.g. a snark worker can be pinned to its own pool,
+//! away from the threads of the node. Reconfiguring a pool replaces it: work already
+//! running keeps the pool it started on.
+//!
+//! The proof limit of a pool, `max_concurrent_proofs`, bounds the number of proofs created
+//! at once in it, by any of the `*_plonk_proof_create*` functions. It counts proofs, not
+//! bytes: a proof over a domain of 2^16 rows counts as much as one over 2^20 rows, although
+//! the memory of a proof grows with its domain. To cap the peak memory of a pool, set the
+//! limit from the largest circuit proved in it. Indexes and accumulators are not counted.
+
+use once_cell::sync::Lazy;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlProverPoolConfig {
+    /// The name of the pool, also the prefix of the names of its threads
+    pub name: String,
+    /// Defaults to the number of CPUs
+    pub num_threads: Option<ocaml::Int>,
+    /// In bytes, defaults to rayon's default
+    pub stack_size: Option<ocaml::Int>,
+    /// How many proofs are created at once in the pool, whatever their domain sizes.
+    /// Defaults to no limit.
+    pub max_concurrent_proofs: Option<ocaml::Int>,
+}
+
+struct ProverPool {
+    pool: ThreadPool,
+    max_concurrent_proofs: Option<usize>,
+    /// How many more proofs can start, if limited
+    available_proofs: Mutex<usize>,
+    proof_finished: Condvar,
+}
+
+impl ProverPool {
+    fn new(pool: ThreadPool, max_concurrent_proofs: Option<usize>) -> Self {
+        ProverPool {
+            pool,
+            max_concurrent_proofs,
+            available_proofs: Mutex::new(max_concurrent_proofs.unwrap_or(0)),
+            proof_finished: Condvar::new(),
+        }
+    }
+
+    /// Waits until `proofs` more proofs fit in the proof limit of the pool, and counts them
+    /// against it until the returned guard is dropped.
+    fn acquire(&self, proofs: usize) -> ProofSlots<'_> {
+        let Some(max) = self.max_concurrent_proofs else {
+            return ProofSlots(self, 0);
+        };
+        // a pool is never asked for more than its limit, which would never be available
+        let proofs = proofs.min(max);
+        let mut available = self
+            .available_proofs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        while *available < proofs {
+            available = self
+                .proof_finished
+                .wait(available)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        *available -= proofs;
+        ProofSlots(self, proofs)
+    }
+}
+
+/// Proofs counted against the limit of a pool, given back when dropped.
+struct ProofSlots<'a>(&'a ProverPool, usize);
+
+impl Drop for ProofSlots<'_> {
+    fn drop(&mut self) {
+        let ProofSlots(pool, proofs) = *self;
+        if proofs > 0 {
+            *pool
+                .available_proofs
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) += proofs;
+            pool.proof_finished.notify_all();
+        }
+    }
+}
+
+fn build_pool(
+    name: &str,
+    num_threads: usize,
+    stack_size: Option<usize>,
+) -> Result<ThreadPool, rayon::ThreadPoolBuildError> {
+    let name = name.to_string();
+    let mut builder = ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .thread_name(move |i| format!("{name}-{i}"));
+    if let Some(stack_size) = stack_size {
+        builder = builder.stack_size(stack_size);
+    }
+    builder.build()
+}
+
+/// The name of the pool that threads use until they select another one.
+pub const DEFAULT_POOL: &str = "kimchi-prover";
+
+static PROVER_POOLS: Lazy<RwLock<HashMap<String, Arc<ProverPool>>>> = Lazy::new(|| {
+    let pool = build_pool(DEFAULT_POOL, 0, None).expect("failed to create the prover thread pool");
+    let pools = HashMap::from([(
+        DEFAULT_POOL.to_string(),
+        Arc::new(ProverPool::new(pool, None)),
+    )]);
+    RwLock::new(pools)
+});
+
+thread_local! {
+    /// The pool selected by the OCaml thread running on this thread, if not the default one
+    static SELECTED_POOL: RefCell<Option<String>> = RefCell::new(None);
+}
+
+fn current() -> Arc<ProverPool> {
+    let pools = PROVER_POOLS.read().unwrap_or_else(|e| e.into_inner());
+    SELECTED_POOL.with(|selected| {
+        selected
+            .borrow()
+            .as_ref()
+            .and_then(|name| pools.get(name))
+            .unwrap_or_else(|| &pools[DEFAULT_POOL])
+            .clone()
+    })
+}
+
+/// Runs `f` in the prover thread pool selected by the calling thread.
+pub fn install<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+    current().pool.install(f)
+}
+
+/// Same as [install], for `f` creating `proofs` proofs at once: waits for the proof limit
+/// of the pool (its `max_concurrent_proofs`) to allow them first.
+/// The runtime must be released, since this may block.
+pub fn prove<R: Send>(proofs: usize, f: impl FnOnce() -> R + Send) -> R {
+    let pool = current();
+    let _slots = pool.acquire(proofs);
+    pool.pool.install(f)
+}
+
+/// How many proofs the pool selected by the calling thread creates at once, if limited.
+pub fn max_concurrent_proofs() -> Option<usize> {
+    current().max_concurrent_proofs
+}
+
+fn non_negative(x: Option<ocaml::Int>, name: &'static str) -> Result<Option<usize>, ocaml::Error> {
+    x.map(|x| usize::try_from(x).map_err(|_| ocaml::Error::invalid_argument(name).err().unwrap()))
+        .transpose()
+}
+
+/// Creates the prover thread pool named `config.name`, or replaces it if it exists.
+/// The pool named [DEFAULT_POOL] is the one used by threads that did not select another.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_prover_pool_configure(config: CamlProverPoolConfig) -> Result<(), ocaml::Error> {
+    let num_threads = non_negative(config.num_threads, "num_threads")?.unwrap_or(0);
+    let stack_size = non_negative(config.stack_size, "stack_size")?;
+    let max_concurrent_proofs =
+        non_negative(config.max_concurrent_proofs, "max_concurrent_proofs")?.map(|max| max.max(1));
+
+    let pool = build_pool(&config.name, num_threads, stack_size)?;
+    PROVER_POOLS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(
+            config.name,
+            Arc::new(ProverPool::new(pool, max_concurrent_proofs)),
+        );
+    Ok(())
+}
+
+/// Makes the calling thread create its indexes, proofs and accumulators in the pool
+/// named `name`, or in the default pool if `None`. The pool must have been configured.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_prover_pool_select(name: Option<String>) -> Result<(), ocaml::Error> {
+    if let Some(name) = &name {
+        let pools = PROVER_POOLS.read().unwrap_or_else(|e| e.into_inner());
+        if !pools.contains_key(name) {
+            return Err(ocaml::Error::invalid_argument("caml_prover_pool_select")
+                .err()
+                .unwrap());
+        }
+    }
+    SELECTED_POOL.with(|selected| *selected.borrow_mut() = name);
+    Ok(())
+}
+
+/// The number of threads of the pool selected by the calling thread.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_prover_pool_num_threads() -> ocaml::Int {
+    current().pool.current_num_threads() as ocaml::Int
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn proofs_wait_for_the_limit_of_their_pool() {
+        let pool = Arc::new(ProverPool::new(
+            build_pool("test-prover", 1, None).unwrap(),
+            Some(2),
+        ));
+        // more than the limit takes all of it, instead of waiting forever
+        let batch = pool.acquire(5);
+        assert_eq!(batch.1, 2);
+
+        let (started, proof_started) = mpsc::channel();
+        let waiting = {
+            let pool = pool.clone();
+            std::thread::spawn(move || {
+                let _slots = pool.acquire(1);
+                started.send(()).unwrap();
+            })
+        };
+        assert!(proof_started
+            .recv_timeout(Duration::from_millis(100))
+            .is_err());
+        drop(batch);
+        proof_started.recv_timeout(Duration::from_secs(10)).unwrap();
+        waiting.join().unwrap();
+    }
+}
@@ -0,0 +1,218 @@
+//! A handle shared between OCaml and a running prover, used to cancel
+//! a proof and to poll its phase.
+//!
+//! Kimchi neither reports progress from within `ProverProof::create_recursive` nor
+//! lets it be interrupted, so the whole of the proving (witness interpolation, quotient,
+//! opening proof) is a single phase, and cancellation is only observed between phases:
+//! a proof that is already in the kimchi prover runs to completion, and is then discarded.
+//! Every proof ends in `Done`, `Cancelled` or `Failed`.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq, ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Enum)]
+pub enum CamlProvingPhase {
+    /// The proof has not started yet
+    Queued,
+    /// Copying the witness and setting up the SRS and group map
+    Preparing,
+    /// Running the kimchi prover
+    Proving,
+    /// Converting the proof for OCaml
+    Finishing,
+    Done,
+    Cancelled,
+    /// The witness or the prover failed, for the given reason
+    Failed(String),
+}
+
+impl CamlProvingPhase {
+    fn is_final(&self) -> bool {
+        matches!(
+            self,
+            CamlProvingPhase::Done | CamlProvingPhase::Cancelled | CamlProvingPhase::Failed(_)
+        )
+    }
+}
+
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlProvingStatus {
+    pub phase: CamlProvingPhase,
+    /// Milliseconds since the proof started, or until it ended,
+    /// or 0 if it is still queued
+    pub elapsed_ms: ocaml::Int,
+}
+
+#[derive(Debug)]
+struct ProvingState {
+    phase: CamlProvingPhase,
+    started: Option<Instant>,
+    /// How long the proof took, once it has ended
+    took: Option<Duration>,
+}
+
+impl ProvingState {
+    fn set(&mut self, phase: CamlProvingPhase) {
+        if phase.is_final() {
+            self.took = self.started.map(|start| start.elapsed());
+        }
+        self.phase = phase;
+    }
+}
+
+/// Tracks a single proof: a task cannot be reused once its proof has started.
+#[derive(Debug)]
+pub struct ProvingTask {
+    cancelled: AtomicBool,
+    state: Mutex<ProvingState>,
+}
+
+impl Default for ProvingTask {
+    fn default() -> Self {
+        ProvingTask {
+            cancelled: AtomicBool::new(false),
+            state: Mutex::new(ProvingState {
+                phase: CamlProvingPhase::Queued,
+                started: None,
+                took: None,
+            }),
+        }
+    }
+}
+
+impl ProvingTask {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Starts the proof: moves the task from `Queued` to `Preparing`, or to `Cancelled`
+    /// if it was cancelled while queued, in which case `Ok(false)` is returned.
+    /// Fails if the task has already been used for a proof.
+    pub fn start(&self) -> Result<bool, &'static str> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.phase != CamlProvingPhase::Queued {
+            return Err("the proving task was already used for a proof");
+        }
+        state.started = Some(Instant::now());
+        if self.is_cancelled() {
+            state.set(CamlProvingPhase::Cancelled);
+            return Ok(false);
+        }
+        state.set(CamlProvingPhase::Preparing);
+        Ok(true)
+    }
+
+    /// Moves the task to `phase`, unless it was cancelled, in which case
+    /// the task is marked as such and `false` is returned.
+    pub fn enter(&self, phase: CamlProvingPhase) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if self.is_cancelled() {
+            state.set(CamlProvingPhase::Cancelled);
+            return false;
+        }
+        state.set(phase);
+        true
+    }
+
+    /// Marks the proof as done, even if it was cancelled during its last phase.
+    pub fn done(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.set(CamlProvingPhase::Done);
+    }
+
+    /// Marks the proof as failed because of `e`, and returns `e`.
+    pub fn failed(&self, e: ocaml::Error) -> ocaml::Error {
+        let reason = match &e {
+            ocaml::Error::Message(msg) => msg.to_string(),
+            ocaml::Error::Error(e) => e.to_string(),
+            _ => "an OCaml exception was raised".to_string(),
+        };
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.set(CamlProvingPhase::Failed(reason));
+        e
+    }
+
+    pub fn status(&self) -> CamlProvingStatus {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let elapsed = state
+            .took
+            .or_else(|| state.started.map(|start| start.elapsed()));
+        CamlProvingStatus {
+            phase: state.phase.clone(),
+            elapsed_ms: elapsed
+                .map(|elapsed| elapsed.as_millis() as ocaml::Int)
+                .unwrap_or(0),
+        }
+    }
+}
+
+impl_shared_reference!(CamlProvingTask => ProvingTask);
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_proving_task_create() -> CamlProvingTask {
+    CamlProvingTask::new(ProvingTask::default())
+}
+
+/// Asks the prover to stop. The proof being created with this task returns `None`
+/// once its current phase ends; in the `Proving` phase, that is once kimchi is done.
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_proving_task_cancel(task: CamlProvingTask) {
+    task.cancel()
+}
+
+#[ocaml_gen::func]
+#[ocaml::func]
+pub fn caml_proving_task_status(task: CamlProvingTask) -> CamlProvingStatus {
+    task.status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_task_tracks_a_single_proof() {
+        let task = ProvingTask::default();
+        assert_eq!(task.status().phase, CamlProvingPhase::Queued);
+        assert_eq!(task.start(), Ok(true));
+        assert!(task.enter(CamlProvingPhase::Proving));
+        task.done();
+        assert_eq!(task.status().phase, CamlProvingPhase::Done);
+        assert!(task.start().is_err());
+    }
+
+    #[test]
+    fn a_failed_proof_ends_the_task() {
+        let task = ProvingTask::default();
+        assert_eq!(task.start(), Ok(true));
+        let e = task.failed(ocaml::Error::Message("the witness is too short"));
+        assert!(matches!(
+            e,
+            ocaml::Error::Message("the witness is too short")
+        ));
+        let status = task.status();
+        assert_eq!(
+            status.phase,
+            CamlProvingPhase::Failed("the witness is too short".to_string())
+        );
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(task.status().elapsed_ms, status.elapsed_ms);
+    }
+
+    #[test]
+    fn a_task_cancelled_while_queued_does_not_start() {
+        let task = ProvingTask::default();
+        task.cancel();
+        assert_eq!(task.start(), Ok(false));
+        assert_eq!(task.status().phase, CamlProvingPhase::Cancelled);
+    }
+}
@@ -0,0 +1,143 @@
+//! Errors that can happen while building a constraint system,
+//! in a form that OCaml can match on.
+
+use ark_ff::PrimeField;
+use ark_poly::EvaluationDomain;
+use kimchi::circuits::{
+    constraints::ConstraintSystem,
+    gate::CircuitGate,
+    lookup::{
+        lookups::LookupFeatures,
+        runtime_tables::RuntimeTableCfg,
+        tables::{get_table, LookupTable},
+    },
+};
+use kimchi::error::SetupError;
+use std::collections::HashSet;
+
+#[derive(Debug, ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Enum)]
+pub enum CamlSetupError {
+    /// The circuit needs a domain larger than the SRS can commit to without chunking:
+    /// (size of the domain, `max_poly_size` of the SRS)
+    DomainTooLarge((ocaml::Int, ocaml::Int)),
+    /// Kimchi could not create the evaluation domain, for the given reason
+    DomainCreation(String),
+    /// Two lookup tables (fixed or runtime) share this ID
+    LookupTableIdCollision(ocaml::Int),
+    /// The runtime table with this ID is misconfigured, for the given reason
+    RuntimeTableMisconfigured((ocaml::Int, String)),
+    /// The circuit declares more public inputs than it has gates:
+    /// (number of public inputs, number of gates)
+    WrongNumberOfPublicInputs((ocaml::Int, ocaml::Int)),
+    /// Any other error reported by kimchi
+    Other(String),
+}
+
+impl std::fmt::Display for CamlSetupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CamlSetupError::DomainTooLarge((domain_size, max_poly_size)) => write!(
+                f,
+                "the circuit needs a domain of size {domain_size}, but the SRS only has {max_poly_size} points"
+            ),
+            CamlSetupError::DomainCreation(e) => {
+                write!(f, "could not create the evaluation domain: {e}")
+            }
+            CamlSetupError::LookupTableIdCollision(id) => {
+                write!(f, "more than one lookup table uses the ID {id}")
+            }
+            CamlSetupError::RuntimeTableMisconfigured((id, reason)) => {
+                write!(f, "the runtime table with ID {id} is misconfigured: {reason}")
+            }
+            CamlSetupError::WrongNumberOfPublicInputs((public, gates)) => write!(
+                f,
+                "the circuit declares {public} public inputs, but only has {gates} gates"
+            ),
+            CamlSetupError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for CamlSetupError {}
+
+impl From<SetupError> for CamlSetupError {
+    fn from(e: SetupError) -> Self {
+        match e {
+            SetupError::DomainCreation(e) => CamlSetupError::DomainCreation(e.to_string()),
+            e => CamlSetupError::Other(e.to_string()),
+        }
+    }
+}
+
+/// Checks the inputs of a constraint system for the errors that kimchi
+/// would otherwise only report as a string, or not at all.
+pub fn check_setup<F: PrimeField>(
+    gates: &[CircuitGate<F>],
+    public: usize,
+    lookup_tables: &[LookupTable<F>],
+    runtime_tables: &[RuntimeTableCfg<F>],
+) -> Result<(), CamlSetupError> {
+    let num_gates = gates.len();
+    if public > num_gates {
+        return Err(CamlSetupError::WrongNumberOfPublicInputs((
+            public as isize,
+            num_gates as isize,
+        )));
+    }
+
+    // the tables that kimchi adds itself, for the XOR, range-check and
+    // foreign field multiplication gates
+    let features = LookupFeatures::from_gates(gates, !runtime_tables.is_empty());
+    let gate_tables: Vec<LookupTable<F>> = features
+        .patterns
+        .into_iter()
+        .filter_map(|pattern| pattern.table())
+        .map(get_table)
+        .collect();
+
+    let mut fixed_ids = HashSet::new();
+    for table in lookup_tables {
+        if !fixed_ids.insert(table.id) {
+            return Err(CamlSetupError::LookupTableIdCollision(table.id as isize));
+        }
+        // a table of the circuit may repeat one of kimchi's, but not replace it
+        if gate_tables
+            .iter()
+            .any(|gate_table| gate_table.id == table.id && gate_table.data != table.data)
+        {
+            return Err(CamlSetupError::LookupTableIdCollision(table.id as isize));
+        }
+    }
+    fixed_ids.extend(gate_tables.iter().map(|table| table.id));
+
+    // runtime tables share the ID space of the fixed lookup tables
+    for cfg in runtime_tables {
+        if !fixed_ids.insert(cfg.id) {
+            return Err(CamlSetupError::LookupTableIdCollision(cfg.id as isize));
+        }
+        if cfg.first_column.is_empty() {
+            return Err(CamlSetupError::RuntimeTableMisconfigured((
+                cfg.id as isize,
+                "the first column is empty".to_string(),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the domain kimchi chose for `cs` fits in an SRS of `max_poly_size` points.
+/// Only circuits whose polynomials are not split in chunks need this.
+pub fn check_domain<F: PrimeField>(
+    cs: &ConstraintSystem<F>,
+    max_poly_size: usize,
+) -> Result<(), CamlSetupError> {
+    let domain_size = cs.domain.d1.size();
+    if domain_size > max_poly_size {
+        return Err(CamlSetupError::DomainTooLarge((
+            domain_size as isize,
+            max_poly_size as isize,
+        )));
+    }
+    Ok(())
+}
@@ -1,17 +1,158 @@
 use ark_poly::UVPolynomial;
-use ark_poly::{univariate::DensePolynomial, EvaluationDomain, Evaluations};
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, Evaluations, Radix2EvaluationDomain,
+};
+use once_cell::sync::Lazy;
 use paste::paste;
 use poly_commitment::SRS as _;
 use poly_commitment::{
-    commitment::{b_poly_coefficients, caml::CamlPolyComm},
+    commitment::{b_poly_coefficients, caml::CamlPolyComm, CommitmentCurve},
     srs::SRS,
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fs::{File, OpenOptions},
     io::{BufReader, BufWriter, Seek, SeekFrom::Start},
+    sync::{Arc, Mutex, PoisonError, RwLock, RwLockReadGuard},
 };
 
+/// Guards the Lagrange bases of one SRS. The SRSs are shared with OCaml behind an `Arc`,
+/// but their Lagrange bases are added lazily, in place: a basis is computed without any lock,
+/// then inserted with `bases` held for writing, while the code that reads the bases holds it
+/// for reading (see [LagrangeBases]).
+#[derive(Default)]
+struct LagrangeBasesLock {
+    bases: RwLock<()>,
+    /// One lock per domain size, so that each basis is computed only once.
+    adding: Mutex<HashMap<usize, Arc<Mutex<()>>>>,
+}
+
+/// The locks of the SRSs, keyed by the address of the SRS. They are never freed:
+/// an SRS allocated where a freed one was simply shares its lock.
+static LAGRANGE_BASES_LOCKS: Lazy<Mutex<HashMap<usize, &'static LagrangeBasesLock>>> =
+    Lazy::new(Default::default);
+
+fn lagrange_bases_lock<G>(srs: &Arc<SRS<G>>) -> &'static LagrangeBasesLock {
+    *LAGRANGE_BASES_LOCKS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(Arc::as_ptr(srs) as usize)
+        .or_insert_with(|| Box::leak(Box::default()))
+}
+
+/// Makes sure `srs` contains the Lagrange basis for `domain`, adding it in place if needed.
+///
+/// The basis is computed without holding any lock, but inserting it waits until no other
+/// thread reads the bases of `srs`: call this with the OCaml runtime released, and never
+/// while holding a [LagrangeBasesGuard].
+pub fn add_lagrange_basis<G: CommitmentCurve>(
+    srs: &Arc<SRS<G>>,
+    domain: Radix2EvaluationDomain<G::ScalarField>,
+) {
+    let lock = lagrange_bases_lock(srs);
+    let size = domain.size();
+    let has_basis = || {
+        let _bases = lock.bases.read().unwrap_or_else(PoisonError::into_inner);
+        srs.lagrange_bases.contains_key(&size)
+    };
+    if has_basis() {
+        return;
+    }
+
+    let adding = lock
+        .adding
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(size)
+        .or_default()
+        .clone();
+    let _adding = adding.lock().unwrap_or_else(PoisonError::into_inner);
+    // another thread may have added it while we waited
+    if has_basis() {
+        return;
+    }
+
+    // compute the basis from a copy of the generators, so that the bases stay readable meanwhile
+    let mut basis_srs = SRS::<G> {
+        g: srs.g.clone(),
+        h: srs.h,
+        lagrange_bases: Default::default(),
+    };
+    basis_srs.add_lagrange_basis(domain);
+    let basis = basis_srs
+        .lagrange_bases
+        .remove(&size)
+        .expect("the basis was just added");
+
+    let _bases = lock.bases.write().unwrap_or_else(PoisonError::into_inner);
+    // SAFETY: only the bases are mutated, and the threads that read them hold the lock
+    // for reading.
+    let bases = unsafe { &mut *std::ptr::addr_of!((*Arc::as_ptr(srs)).lagrange_bases).cast_mut() };
+    bases.insert(size, basis);
+}
+
+/// The Lagrange bases that some code reads: each one is added with [LagrangeBases::with],
+/// then [LagrangeBases::read] keeps them all from changing while the code runs.
+#[derive(Default)]
+pub struct LagrangeBases(Vec<&'static LagrangeBasesLock>);
+
+/// Keeps the Lagrange bases of some SRSs from being added to while it lives.
+pub struct LagrangeBasesGuard {
+    _bases: Vec<RwLockReadGuard<'static, ()>>,
+}
+
+impl LagrangeBases {
+    /// Adds the Lagrange basis of `srs` for `domain` if needed (see [add_lagrange_basis]).
+    pub fn with<G: CommitmentCurve>(
+        mut self,
+        srs: &Arc<SRS<G>>,
+        domain: Radix2EvaluationDomain<G::ScalarField>,
+    ) -> Self {
+        add_lagrange_basis(srs, domain);
+        self.0.push(lagrange_bases_lock(srs));
+        self
+    }
+
+    /// Holds the bases of the SRSs passed to [LagrangeBases::with] for reading.
+    pub fn read(mut self) -> LagrangeBasesGuard {
+        // always lock in the same order, so that two readers waiting on writers cannot deadlock
+        self.0.sort_by_key(|lock| *lock as *const LagrangeBasesLock);
+        self.0
+            .dedup_by_key(|lock| *lock as *const LagrangeBasesLock);
+        LagrangeBasesGuard {
+            _bases: self
+                .0
+                .into_iter()
+                .map(|lock| lock.bases.read().unwrap_or_else(PoisonError::into_inner))
+                .collect(),
+        }
+    }
+}
+
+/// Adds the Lagrange basis of `srs` for `domain` if needed, and keeps the bases of `srs`
+/// from being added to while the guard lives. Call this with the OCaml runtime released.
+pub fn lagrange_basis<G: CommitmentCurve>(
+    srs: &Arc<SRS<G>>,
+    domain: Radix2EvaluationDomain<G::ScalarField>,
+) -> LagrangeBasesGuard {
+    LagrangeBases::default().with(srs, domain).read()
+}
+
+/// Releases the OCaml runtime while running `f`, so that other OCaml threads can run meanwhile.
+///
+/// `f` must not touch the OCaml heap: convert its inputs to Rust values first.
+/// It also adds and reads the Lagrange bases it needs (see [lagrange_basis]),
+/// since adding one may wait for other threads.
+pub fn releasing_runtime<T>(f: impl FnOnce() -> T) -> T {
+    // NB: This is designed only to be used by tests. However, since creating a new reference will cause `drop` to be called on it once we are done with it. Since `drop` calls `caml_shutdown` internally, we *really, really* do not want to do this, but we have no other way to get at the active runtime.
+    // TODO: There's actually a way to get a handle to the runtime as a function argument. Switch
+    // to doing this instead.
+    let runtime = unsafe { ocaml::Runtime::recover_handle() };
+
+    runtime.releasing_runtime(f)
+}
+
 macro_rules! impl_srs {
     ($name: ident, $CamlF: ty, $CamlG: ty, $F: ty, $G: ty) => {
 
@@ -84,12 +225,7 @@ macro_rules! impl_srs {
                         .unwrap()
                 })?;
 
-                {
-                    // We're single-threaded, so it's safe to grab this pointer as mutable.
-                    // Do not try this at home.
-                    let srs = unsafe { &mut *((&**srs as *const SRS<$G>) as *mut SRS<$G>) as &mut SRS<$G> };
-                    srs.add_lagrange_basis(x_domain);
-                }
+                let _bases = releasing_runtime(|| lagrange_basis(&srs.0, x_domain));
 
                 Ok(srs.lagrange_bases[&x_domain.size()][i as usize].clone().into())
             }
@@ -100,10 +236,8 @@ macro_rules! impl_srs {
                 srs: $name,
                 log2_size: ocaml::Int,
             ) {
-                let ptr: &mut poly_commitment::srs::SRS<$G> =
-                    unsafe { &mut *(std::sync::Arc::as_ptr(&srs) as *mut _) };
                 let domain = EvaluationDomain::<$F>::new(1 << (log2_size as usize)).expect("invalid domain size");
-                ptr.add_lagrange_basis(domain);
+                releasing_runtime(|| add_lagrange_basis(&srs.0, domain));
             }
 
             #[ocaml_gen::func]
@@ -147,7 +281,9 @@ macro_rules! impl_srs {
             ) -> bool {
                 let comms: Vec<_> = comms.into_iter().map(Into::into).collect();
                 let chals: Vec<_> = chals.into_iter().map(Into::into).collect();
-                crate::urs_utils::batch_dlog_accumulator_check(&srs, &comms, &chals)
+                crate::prover_pool::install(|| {
+                    crate::urs_utils::batch_dlog_accumulator_check(&srs, &comms, &chals)
+                })
             }
 
             #[ocaml_gen::func]
@@ -157,11 +293,14 @@ macro_rules! impl_srs {
                 comms: ocaml::Int,
                 chals: Vec<$CamlF>,
             ) -> Vec<$CamlG> {
-                crate::urs_utils::batch_dlog_accumulator_generate::<$G>(
-                    &srs,
-                    comms as usize,
-                    &chals.into_iter().map(From::from).collect(),
-                ).into_iter().map(Into::into).collect()
+                let chals = chals.into_iter().map(From::from).collect();
+                crate::prover_pool::install(|| {
+                    crate::urs_utils::batch_dlog_accumulator_generate::<$G>(
+                        &srs,
+                        comms as usize,
+                        &chals,
+                    )
+                }).into_iter().map(Into::into).collect()
             }
 
             #[ocaml_gen::func]
@@ -0,0 +1,225 @@
+//! The Fiat-Shamir transcript of the oracles of a proof, recorded by sponges
+//! that log every absorbed and squeezed value, to compare verifiers with each other.
+
+use crate::oracles::CamlOracles;
+use kimchi::proof::{PointEvaluations, ProofEvaluations};
+
+#[derive(Clone, Copy, PartialEq, Eq, ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Enum)]
+pub enum CamlSponge {
+    /// The sponge over the base field, absorbing commitments
+    FqSponge,
+    /// The sponge over the scalar field, absorbing evaluations
+    FrSponge,
+}
+
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Enum)]
+pub enum CamlTranscriptValue<F, G, BaseF> {
+    Point(G),
+    Scalar(F),
+    BaseField(BaseF),
+}
+
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlTranscriptEntry<F, G, BaseF> {
+    pub sponge: CamlSponge,
+    /// The challenge that was squeezed, or that an absorbed value is bound into
+    pub step: String,
+    /// `false` for absorbed values
+    pub squeezed: bool,
+    pub value: CamlTranscriptValue<F, G, BaseF>,
+}
+
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlOraclesWithTranscript<F, G, BaseF> {
+    pub oracles: CamlOracles<F>,
+    pub transcript: Vec<CamlTranscriptEntry<F, G, BaseF>>,
+}
+
+/// How a value went through a sponge.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SpongeOperation {
+    Absorb,
+    /// A challenge, in the order in which the protocol squeezes them
+    Challenge,
+    /// The digest of the sponge, the one of the Fq-sponge starts the Fr-sponge
+    Digest,
+    /// The digest of the verifier index, which starts the Fq-sponge
+    IndexDigest,
+}
+
+/// A value absorbed or squeezed by a recording sponge.
+pub struct TranscriptEvent<F, G, BaseF> {
+    pub sponge: CamlSponge,
+    pub operation: SpongeOperation,
+    pub value: CamlTranscriptValue<F, G, BaseF>,
+}
+
+/// The names of the challenges squeezed by the Fq-sponge, in the order of kimchi's verifier.
+/// The opening proof then squeezes one prechallenge per round.
+pub fn fq_challenge_names(joint_lookup_used: bool) -> Vec<&'static str> {
+    let mut names = vec![];
+    if joint_lookup_used {
+        names.push("joint_combiner");
+    }
+    names.extend(["beta", "gamma", "alpha", "zeta"]);
+    names
+}
+
+/// The names of the challenges squeezed by the Fr-sponge, in the order of kimchi's verifier.
+pub const FR_CHALLENGE_NAMES: [&str; 2] = ["v (polyscale)", "u (evalscale)"];
+
+/// Converts the events of the recording sponges for OCaml, naming the step of each:
+/// a squeezed value is named after its challenge, and an absorbed value after the next
+/// value squeezed by the same sponge.
+pub fn label_transcript<F, G, BaseF, CamlF, CamlG, CamlBaseF>(
+    events: Vec<TranscriptEvent<F, G, BaseF>>,
+    fq_challenges: &[&str],
+) -> Vec<CamlTranscriptEntry<CamlF, CamlG, CamlBaseF>>
+where
+    CamlF: From<F>,
+    CamlG: From<G>,
+    CamlBaseF: From<BaseF>,
+{
+    // name every squeeze, per sponge
+    let mut challenges = [0, 0];
+    let squeeze_names: Vec<Option<String>> = events
+        .iter()
+        .map(|event| {
+            let (count, names) = match event.sponge {
+                CamlSponge::FqSponge => (&mut challenges[0], fq_challenges),
+                CamlSponge::FrSponge => (&mut challenges[1], &FR_CHALLENGE_NAMES[..]),
+            };
+            match event.operation {
+                SpongeOperation::Absorb => None,
+                SpongeOperation::IndexDigest => Some("verifier_index_digest".to_string()),
+                SpongeOperation::Digest => Some(match event.sponge {
+                    CamlSponge::FqSponge => "fq_digest".to_string(),
+                    CamlSponge::FrSponge => "fr_digest".to_string(),
+                }),
+                SpongeOperation::Challenge => {
+                    let name = match names.get(*count) {
+                        Some(name) => name.to_string(),
+                        None => format!("prechallenge {}", *count - names.len()),
+                    };
+                    *count += 1;
+                    Some(name)
+                }
+            }
+        })
+        .collect();
+
+    // absorbed values take the name of the next squeeze of their sponge
+    let mut next = [None::<String>, None::<String>];
+    let mut steps: Vec<String> = events
+        .iter()
+        .zip(&squeeze_names)
+        .rev()
+        .map(|(event, name)| {
+            let next = &mut next[(event.sponge == CamlSponge::FrSponge) as usize];
+            match name {
+                Some(name) => {
+                    *next = Some(name.clone());
+                    name.clone()
+                }
+                None => next
+                    .clone()
+                    .unwrap_or_else(|| "after the last challenge".to_string()),
+            }
+        })
+        .collect();
+    steps.reverse();
+
+    events
+        .into_iter()
+        .zip(steps)
+        .map(|(event, step)| CamlTranscriptEntry {
+            sponge: event.sponge,
+            step,
+            squeezed: event.operation != SpongeOperation::Absorb,
+            value: match event.value {
+                CamlTranscriptValue::Point(g) => CamlTranscriptValue::Point(g.into()),
+                CamlTranscriptValue::Scalar(x) => CamlTranscriptValue::Scalar(x.into()),
+                CamlTranscriptValue::BaseField(x) => CamlTranscriptValue::BaseField(x.into()),
+            },
+        })
+        .collect()
+}
+
+/// The evaluations of a proof in the order in which kimchi's Fr-sponge absorbs them:
+/// the evaluations at zeta then at zeta * omega of each polynomial, starting with z and
+/// the selectors of the generic and elliptic curve gates, then w, the coefficients and s,
+/// then the optional polynomials that are present. `public` is not included, as the
+/// verifier absorbs it separately, before all the others.
+pub fn evaluations_in_absorption_order<F>(
+    e: &ProofEvaluations<PointEvaluations<Vec<F>>>,
+) -> Vec<&PointEvaluations<Vec<F>>> {
+    let ProofEvaluations {
+        public: _,
+        w,
+        z,
+        s,
+        coefficients,
+        generic_selector,
+        poseidon_selector,
+        complete_add_selector,
+        mul_selector,
+        emul_selector,
+        endomul_scalar_selector,
+        range_check0_selector,
+        range_check1_selector,
+        foreign_field_add_selector,
+        foreign_field_mul_selector,
+        xor_selector,
+        rot_selector,
+        lookup_aggregation,
+        lookup_table,
+        lookup_sorted,
+        runtime_lookup_table,
+        runtime_lookup_table_selector,
+        xor_lookup_selector,
+        lookup_gate_lookup_selector,
+        range_check_lookup_selector,
+        foreign_field_mul_lookup_selector,
+    } = e;
+
+    let mut points = vec![
+        z,
+        generic_selector,
+        poseidon_selector,
+        complete_add_selector,
+        mul_selector,
+        emul_selector,
+        endomul_scalar_selector,
+    ];
+    points.extend(w);
+    points.extend(coefficients);
+    points.extend(s);
+    points.extend(
+        [
+            range_check0_selector,
+            range_check1_selector,
+            foreign_field_add_selector,
+            foreign_field_mul_selector,
+            xor_selector,
+            rot_selector,
+            lookup_aggregation,
+            lookup_table,
+        ]
+        .into_iter()
+        .flatten(),
+    );
+    points.extend(lookup_sorted.iter().flatten());
+    points.extend(
+        [
+            runtime_lookup_table,
+            runtime_lookup_table_selector,
+            xor_lookup_selector,
+            lookup_gate_lookup_selector,
+            range_check_lookup_selector,
+            foreign_field_mul_lookup_selector,
+        ]
+        .into_iter()
+        .flatten(),
+    );
+    points
+}
@@ -0,0 +1,339 @@
+//! A field-by-field comparison of two verifier indexes, to find out why two builds
+//! of a circuit do not produce the same verification key.
+
+use crate::linearization::feature_linearization_strings;
+use crate::plonk_verifier_index::verifier_index_feature_flags;
+use ark_ff::{PrimeField, SquareRootField};
+use kimchi::{curve::KimchiCurve, verifier_index::VerifierIndex};
+use poly_commitment::{commitment::PolyComm, evaluation_proof::OpeningProof};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlVerifierIndexChange {
+    /// The path of the field, e.g. `sigma_comm.3` or `lookup_index.lookup_selectors.xor`
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Accumulates the fields that differ between two indexes.
+#[derive(Default)]
+struct Changes(Vec<CamlVerifierIndexChange>);
+
+impl Changes {
+    fn push(&mut self, field: impl Into<String>, before: String, after: String) {
+        self.0.push(CamlVerifierIndexChange {
+            field: field.into(),
+            before,
+            after,
+        })
+    }
+
+    fn value<T: PartialEq + Debug>(&mut self, field: impl Into<String>, before: &T, after: &T) {
+        if before != after {
+            self.push(field, format!("{:?}", before), format!("{:?}", after))
+        }
+    }
+
+    fn comm<G: PartialEq + Debug>(
+        &mut self,
+        field: impl Into<String>,
+        before: Option<&PolyComm<G>>,
+        after: Option<&PolyComm<G>>,
+    ) {
+        if before != after {
+            self.push(field, describe_comm(before), describe_comm(after))
+        }
+    }
+
+    fn comms<G: PartialEq + Debug>(
+        &mut self,
+        field: &str,
+        before: &[PolyComm<G>],
+        after: &[PolyComm<G>],
+    ) {
+        for i in 0..std::cmp::max(before.len(), after.len()) {
+            self.comm(format!("{}.{}", field, i), before.get(i), after.get(i));
+        }
+    }
+}
+
+fn describe_comm<G: Debug>(comm: Option<&PolyComm<G>>) -> String {
+    match comm {
+        None => "absent".to_string(),
+        Some(comm) => format!("{:?} (shifted: {:?})", comm.unshifted, comm.shifted),
+    }
+}
+
+/// The fields of `after` that differ from those of `before`, in the order of the fields of
+/// [VerifierIndex]. The SRS is not compared, and neither are the values that kimchi derives
+/// from the domain.
+pub fn diff_verifier_indexes<G: KimchiCurve>(
+    before: &VerifierIndex<G, OpeningProof<G>>,
+    after: &VerifierIndex<G, OpeningProof<G>>,
+) -> Vec<CamlVerifierIndexChange>
+where
+    G::ScalarField: PrimeField + SquareRootField,
+{
+    let mut changes = Changes::default();
+
+    changes.value("domain.size", &before.domain.size, &after.domain.size);
+    changes.value(
+        "domain.group_gen",
+        &before.domain.group_gen,
+        &after.domain.group_gen,
+    );
+    changes.value("max_poly_size", &before.max_poly_size, &after.max_poly_size);
+    changes.value("zk_rows", &before.zk_rows, &after.zk_rows);
+    changes.value("public", &before.public, &after.public);
+    changes.value(
+        "prev_challenges",
+        &before.prev_challenges,
+        &after.prev_challenges,
+    );
+
+    // the commitments of [CamlPlonkVerificationEvals]
+    changes.comms("sigma_comm", &before.sigma_comm, &after.sigma_comm);
+    changes.comms(
+        "coefficients_comm",
+        &before.coefficients_comm,
+        &after.coefficients_comm,
+    );
+    for (field, b, a) in [
+        ("generic_comm", &before.generic_comm, &after.generic_comm),
+        ("psm_comm", &before.psm_comm, &after.psm_comm),
+        (
+            "complete_add_comm",
+            &before.complete_add_comm,
+            &after.complete_add_comm,
+        ),
+        ("mul_comm", &before.mul_comm, &after.mul_comm),
+        ("emul_comm", &before.emul_comm, &after.emul_comm),
+        (
+            "endomul_scalar_comm",
+            &before.endomul_scalar_comm,
+            &after.endomul_scalar_comm,
+        ),
+    ] {
+        changes.comm(field, Some(b), Some(a));
+    }
+    for (field, b, a) in [
+        ("xor_comm", &before.xor_comm, &after.xor_comm),
+        (
+            "range_check0_comm",
+            &before.range_check0_comm,
+            &after.range_check0_comm,
+        ),
+        (
+            "range_check1_comm",
+            &before.range_check1_comm,
+            &after.range_check1_comm,
+        ),
+        (
+            "foreign_field_add_comm",
+            &before.foreign_field_add_comm,
+            &after.foreign_field_add_comm,
+        ),
+        (
+            "foreign_field_mul_comm",
+            &before.foreign_field_mul_comm,
+            &after.foreign_field_mul_comm,
+        ),
+        ("rot_comm", &before.rot_comm, &after.rot_comm),
+    ] {
+        changes.comm(field, b.as_ref(), a.as_ref());
+    }
+
+    changes.value("shifts", &before.shift, &after.shift);
+
+    match (&before.lookup_index, &after.lookup_index) {
+        (None, None) => (),
+        (Some(_), None) | (None, Some(_)) => changes.push(
+            "lookup_index",
+            presence(before.lookup_index.is_some()),
+            presence(after.lookup_index.is_some()),
+        ),
+        (Some(b), Some(a)) => {
+            changes.value(
+                "lookup_index.joint_lookup_used",
+                &b.joint_lookup_used,
+                &a.joint_lookup_used,
+            );
+            changes.comms(
+                "lookup_index.lookup_table",
+                &b.lookup_table,
+                &a.lookup_table,
+            );
+            let (bs, as_) = (&b.lookup_selectors, &a.lookup_selectors);
+            for (field, b, a) in [
+                ("xor", &bs.xor, &as_.xor),
+                ("lookup", &bs.lookup, &as_.lookup),
+                ("range_check", &bs.range_check, &as_.range_check),
+                ("ffmul", &bs.ffmul, &as_.ffmul),
+            ] {
+                changes.comm(
+                    format!("lookup_index.lookup_selectors.{}", field),
+                    b.as_ref(),
+                    a.as_ref(),
+                );
+            }
+            changes.comm(
+                "lookup_index.table_ids",
+                b.table_ids.as_ref(),
+                a.table_ids.as_ref(),
+            );
+            changes.value(
+                "lookup_index.lookup_info.max_per_row",
+                &b.lookup_info.max_per_row,
+                &a.lookup_info.max_per_row,
+            );
+            changes.value(
+                "lookup_index.lookup_info.max_joint_size",
+                &b.lookup_info.max_joint_size,
+                &a.lookup_info.max_joint_size,
+            );
+            changes.comm(
+                "lookup_index.runtime_tables_selector",
+                b.runtime_tables_selector.as_ref(),
+                a.runtime_tables_selector.as_ref(),
+            );
+        }
+    }
+
+    // the linearization is derived from the feature flags, so it can only change with them
+    let (before_flags, after_flags) = (
+        verifier_index_feature_flags(before),
+        verifier_index_feature_flags(after),
+    );
+    changes.value("feature_flags", &before_flags, &after_flags);
+    if before_flags != after_flags {
+        let (before_constant, before_terms) =
+            feature_linearization_strings::<G::ScalarField>(Some(&before_flags));
+        let (after_constant, after_terms) =
+            feature_linearization_strings::<G::ScalarField>(Some(&after_flags));
+        if before_constant != after_constant {
+            changes.push(
+                "linearization.constant_term",
+                before_constant,
+                after_constant,
+            );
+        }
+
+        let mut terms: BTreeMap<String, (Option<String>, Option<String>)> = BTreeMap::new();
+        for (col, expr) in before_terms {
+            terms.entry(col).or_default().0 = Some(expr);
+        }
+        for (col, expr) in after_terms {
+            terms.entry(col).or_default().1 = Some(expr);
+        }
+        for (col, (b, a)) in terms {
+            if b != a {
+                let absent = || "absent".to_string();
+                changes.push(
+                    format!("linearization.index_terms.{}", col),
+                    b.unwrap_or_else(absent),
+                    a.unwrap_or_else(absent),
+                );
+            }
+        }
+    }
+
+    changes.0
+}
+
+fn presence(present: bool) -> String {
+    if present { "present" } else { "absent" }.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dummy::DummyShape;
+    use crate::pasta_fp_plonk_verifier_index::CamlPastaFpPlonkVerifierIndex;
+    use crate::srs::fp::CamlFpSrs;
+    use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+    use kimchi::circuits::{
+        constraints::FeatureFlags,
+        lookup::lookups::{LookupFeatures, LookupPatterns},
+    };
+    use mina_curves::pasta::Vesta;
+    use poly_commitment::srs::SRS;
+
+    fn feature_flags(xor: bool) -> FeatureFlags {
+        FeatureFlags {
+            range_check0: false,
+            range_check1: false,
+            foreign_field_add: false,
+            foreign_field_mul: false,
+            xor,
+            rot: false,
+            lookup_features: LookupFeatures {
+                patterns: LookupPatterns {
+                    xor,
+                    lookup: false,
+                    range_check: false,
+                    foreign_field_mul: false,
+                },
+                joint_lookup_used: xor,
+                uses_runtime_tables: false,
+            },
+        }
+    }
+
+    fn verifier_index(xor: bool) -> VerifierIndex<Vesta, OpeningProof<Vesta>> {
+        let shape = DummyShape::for_tests(feature_flags(xor));
+        let srs = CamlFpSrs::new(SRS::create(shape.max_poly_size()));
+        let index: CamlPastaFpPlonkVerifierIndex = shape.verifier_index::<Vesta, _, _, _>(srs);
+        index.into()
+    }
+
+    fn fields(changes: &[CamlVerifierIndexChange]) -> Vec<&str> {
+        changes.iter().map(|change| change.field.as_str()).collect()
+    }
+
+    #[test]
+    fn identical_indexes_have_no_changes() {
+        let index = verifier_index(false);
+        assert!(diff_verifier_indexes(&index, &index).is_empty());
+    }
+
+    #[test]
+    fn changed_sizes_are_reported() {
+        let before = verifier_index(false);
+        let mut after = verifier_index(false);
+        after.public += 1;
+        after.domain = Radix2EvaluationDomain::new(2 * before.domain.size()).unwrap();
+
+        let changes = diff_verifier_indexes(&before, &after);
+        let fields = fields(&changes);
+        assert!(fields.contains(&"domain.size"));
+        assert!(fields.contains(&"public"));
+        let public = &changes[fields.iter().position(|f| *f == "public").unwrap()];
+        assert_eq!(public.before, "3");
+        assert_eq!(public.after, "4");
+    }
+
+    #[test]
+    fn changed_feature_flags_report_the_lookups_and_the_linearization() {
+        let changes = diff_verifier_indexes(&verifier_index(false), &verifier_index(true));
+        let fields = fields(&changes);
+        assert!(fields.contains(&"xor_comm"));
+        assert!(fields.contains(&"lookup_index"));
+        assert!(fields.contains(&"feature_flags"));
+        assert!(fields
+            .iter()
+            .any(|field| field.starts_with("linearization.")));
+    }
+
+    #[test]
+    fn a_changed_shifted_part_is_described() {
+        let before = verifier_index(false);
+        let mut after = verifier_index(false);
+        after.generic_comm.shifted = Some(before.generic_comm.unshifted[0]);
+
+        let changes = diff_verifier_indexes(&before, &after);
+        assert_eq!(fields(&changes), ["generic_comm"]);
+        assert_ne!(changes[0].before, changes[0].after);
+    }
+}
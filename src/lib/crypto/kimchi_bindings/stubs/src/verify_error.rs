@@ -0,0 +1,70 @@
+//! Reasons for which a proof does not verify, in a form that OCaml can match on.
+
+use kimchi::error::VerifyError;
+
+#[derive(Debug, ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Enum)]
+pub enum CamlVerifyError {
+    /// The proof does not have as many public inputs as the verifier index:
+    /// (expected, actual)
+    WrongPublicInputLength((ocaml::Int, ocaml::Int)),
+    /// A commitment or an evaluation of the proof has the wrong size, as described
+    IncorrectCommitmentShape(String),
+    /// The opening proof does not verify
+    OpeningProofFailed,
+    /// The proof was not created for this verifier index, as described
+    /// (e.g. a different number of previous challenges, or of lookup features)
+    VerifierIndexMismatch(String),
+    /// Any other error reported by kimchi
+    Other(String),
+}
+
+impl std::fmt::Display for CamlVerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CamlVerifyError::WrongPublicInputLength((expected, actual)) => write!(
+                f,
+                "the proof has {actual} public inputs, but the verifier index expects {expected}"
+            ),
+            CamlVerifyError::IncorrectCommitmentShape(e) => {
+                write!(f, "the proof is malformed: {e}")
+            }
+            CamlVerifyError::OpeningProofFailed => write!(f, "the opening proof does not verify"),
+            CamlVerifyError::VerifierIndexMismatch(e) => {
+                write!(f, "the proof does not match the verifier index: {e}")
+            }
+            CamlVerifyError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for CamlVerifyError {}
+
+impl From<VerifyError> for CamlVerifyError {
+    fn from(e: VerifyError) -> Self {
+        match e {
+            VerifyError::OpenProof => CamlVerifyError::OpeningProofFailed,
+            VerifyError::IncorrectCommitmentLength(..)
+            | VerifyError::IncorrectEvaluationsLength(..) => {
+                CamlVerifyError::IncorrectCommitmentShape(e.to_string())
+            }
+            VerifyError::IncorrectPrevChallengesLength(..)
+            | VerifyError::LookupCommitmentMissing
+            | VerifyError::LookupEvalsMissing
+            | VerifyError::ProofInconsistentLookup => {
+                CamlVerifyError::VerifierIndexMismatch(e.to_string())
+            }
+            e => CamlVerifyError::Other(e.to_string()),
+        }
+    }
+}
+
+/// Checks the number of public inputs of a proof, which kimchi reports without the actual length.
+pub fn check_public_input_length(expected: usize, actual: usize) -> Result<(), CamlVerifyError> {
+    if expected != actual {
+        return Err(CamlVerifyError::WrongPublicInputLength((
+            expected as isize,
+            actual as isize,
+        )));
+    }
+    Ok(())
+}
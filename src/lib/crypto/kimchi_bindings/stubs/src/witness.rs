@@ -0,0 +1,315 @@
+//! Helpers to check the shape of witnesses, and to diagnose witnesses
+//! that do not satisfy their circuit.
+
+use ark_ff::PrimeField;
+use ark_poly::EvaluationDomain;
+use kimchi::circuits::{
+    constraints::{ConstraintSystem, GateError},
+    gate::{CurrOrNext, GateType},
+    lookup::{
+        lookups::{LocalPosition, LookupPattern, LookupTableID},
+        runtime_tables::RuntimeTable,
+    },
+    polynomial::COLUMNS,
+    wires::{caml::CamlWire, PERMUTS},
+};
+use std::collections::HashSet;
+
+/// The values of a row of the witness that is not satisfied.
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlWitnessRow<F> {
+    pub row: ocaml::Int,
+    pub gate_type: GateType,
+    pub curr: Vec<F>,
+    /// Empty if `row` is the last row of the witness
+    pub next: Vec<F>,
+}
+
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Enum)]
+pub enum CamlWitnessFailure<F> {
+    /// The constraints of the gate are not satisfied, with kimchi's explanation
+    Constraint(String),
+    /// The cell in this column is not equal to the cell at the other end of its wire
+    CopyConstraint((ocaml::Int, CamlWire)),
+    /// This (table ID, entry) is not in the lookup table
+    MissingLookup((F, Vec<F>)),
+}
+
+#[derive(ocaml::IntoValue, ocaml::FromValue, ocaml_gen::Struct)]
+pub struct CamlUnsatisfiedWitness<F> {
+    /// Where the witness fails
+    pub location: CamlWitnessRow<F>,
+    pub failure: CamlWitnessFailure<F>,
+}
+
+/// The first failure of a witness, as found by [check_witness].
+pub struct WitnessFailure<F> {
+    pub row: usize,
+    pub failure: CamlWitnessFailure<F>,
+}
+
+impl<F: PrimeField> WitnessFailure<F> {
+    /// Converts the failure for OCaml, attaching the gate and the witness values at its row.
+    pub fn into_caml<CamlF: From<F>>(
+        self,
+        cs: &ConstraintSystem<F>,
+        witness: &[Vec<F>; COLUMNS],
+    ) -> CamlUnsatisfiedWitness<CamlF> {
+        let row_values = |row: usize| -> Vec<CamlF> {
+            if row < witness[0].len() {
+                witness.iter().map(|col| col[row].into()).collect()
+            } else {
+                vec![]
+            }
+        };
+        let row = self.row;
+        let location = CamlWitnessRow {
+            row: row as isize,
+            gate_type: cs
+                .gates
+                .get(row)
+                .map(|gate| gate.typ)
+                .unwrap_or(GateType::Zero),
+            curr: row_values(row),
+            next: row_values(row + 1),
+        };
+        let failure = match self.failure {
+            CamlWitnessFailure::Constraint(err) => CamlWitnessFailure::Constraint(err),
+            CamlWitnessFailure::CopyConstraint(wire) => CamlWitnessFailure::CopyConstraint(wire),
+            CamlWitnessFailure::MissingLookup((id, entry)) => CamlWitnessFailure::MissingLookup((
+                id.into(),
+                entry.into_iter().map(Into::into).collect(),
+            )),
+        };
+        CamlUnsatisfiedWitness { location, failure }
+    }
+}
+
+/// Checks that the witness has [COLUMNS] columns of the same length,
+/// and that this length fits in the domain of the circuit.
+pub fn check_witness_lengths<F: PrimeField>(
+    cs: &ConstraintSystem<F>,
+    column_lengths: &[usize],
+) -> Result<(), String> {
+    if column_lengths.len() != COLUMNS {
+        return Err(format!(
+            "the witness has {} columns, but kimchi expects {COLUMNS}",
+            column_lengths.len()
+        ));
+    }
+    let rows = column_lengths[0];
+    if let Some((col, len)) = column_lengths
+        .iter()
+        .enumerate()
+        .find(|(_, len)| **len != rows)
+    {
+        return Err(format!(
+            "column {col} of the witness has {len} rows, but column 0 has {rows}"
+        ));
+    }
+    if rows < cs.public {
+        return Err(format!(
+            "the witness has {rows} rows, but the circuit has {} public inputs",
+            cs.public
+        ));
+    }
+    let domain_size = cs.domain.d1.size();
+    let zk_rows = cs.zk_rows as usize;
+    if rows > domain_size - zk_rows {
+        return Err(format!(
+            "the witness has {rows} rows, but the domain of size {domain_size} only has room for {} (the last {zk_rows} are reserved for zero-knowledge)",
+            domain_size - zk_rows
+        ));
+    }
+    Ok(())
+}
+
+/// Finds the first row at which a copy constraint is broken.
+fn check_copy_constraints<F: PrimeField>(
+    cs: &ConstraintSystem<F>,
+    witness: &[Vec<F>; COLUMNS],
+) -> Option<WitnessFailure<F>> {
+    let value = |row: usize, col: usize| witness[col].get(row).copied().unwrap_or_else(F::zero);
+    for (row, gate) in cs.gates.iter().enumerate() {
+        for col in 0..PERMUTS {
+            let wire = gate.wires[col];
+            if value(row, col) != value(wire.row, wire.col) {
+                return Some(WitnessFailure {
+                    row,
+                    failure: CamlWitnessFailure::CopyConstraint((col as isize, wire.into())),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Rebuilds the rows of the combined lookup table, as `[table ID, columns...]`.
+fn lookup_table_rows<F: PrimeField>(
+    cs: &ConstraintSystem<F>,
+    runtime_tables: &[RuntimeTable<F>],
+) -> Option<HashSet<Vec<F>>> {
+    let lcs = cs.lookup_constraint_system.as_ref()?;
+    let d1 = cs.domain.d1;
+
+    let mut columns: Vec<Vec<F>> = lcs
+        .lookup_table
+        .iter()
+        .map(|poly| poly.evaluate_over_domain_by_ref(d1).evals)
+        .collect();
+    let ids = match &lcs.table_ids {
+        Some(poly) => poly.evaluate_over_domain_by_ref(d1).evals,
+        None => vec![F::zero(); d1.size()],
+    };
+
+    // the second column of runtime tables is only known at proving time
+    if let (Some(specs), Some(offset)) = (&lcs.runtime_tables, lcs.runtime_table_offset) {
+        let mut row = offset;
+        for spec in specs {
+            let data = runtime_tables.iter().find(|table| table.id == spec.id);
+            for i in 0..spec.len {
+                columns[1][row + i] = data
+                    .and_then(|table| table.data.get(i).copied())
+                    .unwrap_or_else(F::zero);
+            }
+            row += spec.len;
+        }
+    }
+
+    Some(
+        (0..d1.size())
+            .map(|row| {
+                std::iter::once(ids[row])
+                    .chain(columns.iter().map(|col| col[row]))
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// The field element of a lookup table ID.
+fn table_id<F: PrimeField>(id: i32) -> F {
+    let abs = F::from(id.unsigned_abs() as u64);
+    if id < 0 {
+        -abs
+    } else {
+        abs
+    }
+}
+
+/// Finds the first gate querying an entry that is not in its table: the `Lookup` gates,
+/// and the gates that kimchi checks with its built-in tables (XOR, range check
+/// and foreign field multiplication).
+fn check_lookups<F: PrimeField>(
+    cs: &ConstraintSystem<F>,
+    witness: &[Vec<F>; COLUMNS],
+    runtime_tables: &[RuntimeTable<F>],
+) -> Option<WitnessFailure<F>> {
+    let table = lookup_table_rows(cs, runtime_tables)?;
+    let width = table.iter().next().map(|row| row.len()).unwrap_or(1);
+    // rows past the end of the witness are padded with zeros by kimchi
+    let value = |row: usize, col: usize| witness[col].get(row).copied().unwrap_or_else(F::zero);
+
+    for (gate_row, gate) in cs.gates.iter().enumerate() {
+        if gate_row >= witness[0].len() {
+            break;
+        }
+        // the lookups of a gate are on its own row, or on the next one
+        for (curr_or_next, row) in [
+            (CurrOrNext::Curr, gate_row),
+            (CurrOrNext::Next, gate_row + 1),
+        ] {
+            let Some(pattern) = LookupPattern::from_gate(gate.typ, curr_or_next) else {
+                continue;
+            };
+            let eval = |pos: LocalPosition| {
+                let row = match pos.row {
+                    CurrOrNext::Curr => row,
+                    CurrOrNext::Next => row + 1,
+                };
+                value(row, pos.column)
+            };
+            for lookup in pattern.lookups::<F>() {
+                let id = match lookup.table_id {
+                    LookupTableID::Constant(id) => table_id(id),
+                    LookupTableID::WitnessColumn(col) => value(row, col),
+                };
+                let entry: Vec<F> = lookup
+                    .entry
+                    .iter()
+                    .map(|single| single.evaluate(eval))
+                    .collect();
+                let mut padded: Vec<F> = std::iter::once(id).chain(entry.iter().copied()).collect();
+                padded.resize(width, F::zero());
+                if !table.contains(&padded) {
+                    return Some(WitnessFailure {
+                        row,
+                        failure: CamlWitnessFailure::MissingLookup((id, entry)),
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Looks for the first row at which the witness does not satisfy the circuit.
+/// `gate_check` is the result of kimchi's own verification of the witness.
+pub fn check_witness<F: PrimeField>(
+    cs: &ConstraintSystem<F>,
+    witness: &[Vec<F>; COLUMNS],
+    runtime_tables: &[RuntimeTable<F>],
+    gate_check: Result<(), GateError>,
+) -> Option<WitnessFailure<F>> {
+    let gate_failure = match gate_check {
+        Ok(()) => None,
+        Err(GateError::Custom { row, err }) => Some(WitnessFailure {
+            row,
+            failure: CamlWitnessFailure::Constraint(err),
+        }),
+        Err(GateError::DisconnectedWires(cell, wire)) => Some(WitnessFailure {
+            row: cell.row,
+            failure: CamlWitnessFailure::CopyConstraint((cell.col as isize, wire.into())),
+        }),
+        Err(GateError::IncorrectPublic(row)) => Some(WitnessFailure {
+            row,
+            failure: CamlWitnessFailure::Constraint(
+                "the public input is not wired to a generic gate".to_string(),
+            ),
+        }),
+    };
+
+    // keep the failure with the lowest row
+    [
+        gate_failure,
+        check_copy_constraints(cs, witness),
+        check_lookups(cs, witness, runtime_tables),
+    ]
+    .into_iter()
+    .flatten()
+    .min_by_key(|failure| failure.row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kimchi::circuits::{gate::CircuitGate, polynomials::xor};
+    use mina_curves::pasta::Fp;
+
+    #[test]
+    fn xor_lookups_are_checked() {
+        let (_, gates) = CircuitGate::<Fp>::create_xor_gadget(0, 16);
+        let cs = ConstraintSystem::create(gates).build().unwrap();
+        let mut witness = xor::create_xor_witness(Fp::from(0xA5A5u64), Fp::from(0x0FF0u64), 16);
+        assert!(check_lookups(&cs, &witness, &[]).is_none());
+
+        // the first nibble of the first input, which must be in the 4-bit XOR table
+        witness[3][0] = Fp::from(16u64);
+        let failure = check_lookups(&cs, &witness, &[]).unwrap();
+        assert_eq!(failure.row, 0);
+        assert!(matches!(
+            failure.failure,
+            CamlWitnessFailure::MissingLookup((id, _)) if id == Fp::from(0u64)
+        ));
+    }
+}